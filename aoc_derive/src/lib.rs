@@ -0,0 +1,252 @@
+//! `#[derive(Parse)]`, which generates a nom parser for a struct from a format string.
+//!
+//! ```ignore
+//! #[derive(Parse)]
+//! #[parse("Card {id}: {winning: list< >} | {selected: list< >}")]
+//! struct Card {
+//!     id: usize,
+//!     winning: Vec<usize>,
+//!     selected: Vec<usize>,
+//! }
+//! ```
+//!
+//! Text in the format is matched literally, except that a run of spaces matches one or more
+//! spaces. `{field}` parses the field with its `Parse` implementation, and `{field: list<SEP>}`
+//! parses a `Vec` of them separated by `SEP`. `{{` and `}}` match a literal brace.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+#[proc_macro_derive(Parse, attributes(parse))]
+pub fn derive_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Segment {
+    Literal(String),
+    Field {
+        name: String,
+        /// The separator of a `list<SEP>` field.
+        separator: Option<String>,
+    },
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let ident = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "#[derive(Parse)] does not support generics",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    ident.span(),
+                    "#[derive(Parse)] needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                ident.span(),
+                "#[derive(Parse)] can only be used on structs",
+            ))
+        }
+    };
+    let format = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("parse"))
+        .ok_or_else(|| {
+            Error::new(
+                ident.span(),
+                "#[derive(Parse)] needs a format, like #[parse(\"Card {id}: {numbers: list< >}\")]",
+            )
+        })?
+        .parse_args::<LitStr>()?;
+
+    let segments = segments(&format)?;
+    let mut seen = vec![];
+    let mut steps = vec![];
+    for segment in &segments {
+        match segment {
+            Segment::Literal(text) => {
+                let literal = literal(text);
+                steps.push(quote! { let (input, _) = #literal(input)?; });
+            }
+            Segment::Field { name, separator } => {
+                let field = fields
+                    .iter()
+                    .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))
+                    .ok_or_else(|| {
+                        Error::new(
+                            format.span(),
+                            format!("{} has no field called `{}`", ident, name),
+                        )
+                    })?;
+                if seen.contains(name) {
+                    return Err(Error::new(
+                        format.span(),
+                        format!("`{}` is in the format more than once", name),
+                    ));
+                }
+                seen.push(name.clone());
+
+                let ident = &field.ident;
+                let parser = match separator {
+                    None => {
+                        let ty = &field.ty;
+                        quote! { <#ty as crate::parser::Parse>::parse }
+                    }
+                    Some(separator) => {
+                        let element = vec_element(&field.ty).ok_or_else(|| {
+                            Error::new(field.ty.span(), "a list<SEP> field has to be a Vec")
+                        })?;
+                        let separator = literal(separator);
+                        quote! {
+                            ::nom::multi::separated_list1(
+                                #separator,
+                                <#element as crate::parser::Parse>::parse,
+                            )
+                        }
+                    }
+                };
+                steps.push(quote! {
+                    let (input, #ident) = ::nom::error::context(#name, #parser)(input)?;
+                });
+            }
+        }
+    }
+    if let Some(missing) = fields
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .find(|ident| !seen.contains(&ident.to_string()))
+    {
+        return Err(Error::new(
+            format.span(),
+            format!("the format doesn't say where to parse `{}` from", missing),
+        ));
+    }
+
+    let names = fields.iter().map(|field| &field.ident);
+    let context = ident.to_string();
+    Ok(quote! {
+        impl crate::parser::Parse for #ident {
+            fn parse(input: &str) -> crate::parser::ParseResult<'_, Self> {
+                fn fields(input: &str) -> crate::parser::ParseResult<'_, #ident> {
+                    #(#steps)*
+                    Ok((input, #ident { #(#names),* }))
+                }
+                ::nom::error::context(#context, fields)(input)
+            }
+        }
+    })
+}
+
+/// Splits the format into literal text and fields.
+fn segments(format: &LitStr) -> Result<Vec<Segment>, Error> {
+    let error = |message: &str| Error::new(format.span(), message);
+    let value = format.value();
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err(error("unmatched `}`, use `}}` to match a literal brace")),
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => {
+                            return Err(error("unclosed `{`, use `{{` to match a literal brace"))
+                        }
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(match field.split_once(':') {
+                    None => Segment::Field {
+                        name: field.trim().to_owned(),
+                        separator: None,
+                    },
+                    Some((name, kind)) => {
+                        let separator = kind
+                            .trim()
+                            .strip_prefix("list<")
+                            .and_then(|kind| kind.strip_suffix('>'))
+                            .filter(|separator| !separator.is_empty())
+                            .ok_or_else(|| error("expected `{field}` or `{field: list<SEP>}`"))?;
+                        Segment::Field {
+                            name: name.trim().to_owned(),
+                            separator: Some(separator.to_owned()),
+                        }
+                    }
+                });
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// A parser matching `text`, where a run of spaces matches one or more spaces.
+fn literal(text: &str) -> TokenStream2 {
+    let mut parts = vec![];
+    let mut rest = text;
+    while !rest.is_empty() {
+        let spaces = rest.len() - rest.trim_start().len();
+        if spaces > 0 {
+            parts.push(quote! { ::nom::character::complete::space1 });
+            rest = &rest[spaces..];
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let tag = &rest[..end];
+            parts.push(quote! { ::nom::bytes::complete::tag(#tag) });
+            rest = &rest[end..];
+        }
+    }
+    quote! { ::nom::sequence::tuple((#(#parts,)*)) }
+}
+
+/// `T` for a field of type `Vec<T>`.
+fn vec_element(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(element) => Some(element),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,93 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind};
+
+use serde::{Deserialize, Serialize};
+
+/// Verified answers for one year, persisted in `answers/<year>.toml`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Answers {
+    #[serde(flatten)]
+    days: BTreeMap<String, DayAnswers>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+impl DayAnswers {
+    fn part(&self, part: usize) -> Option<&String> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn part_mut(&mut self, part: usize) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            part => panic!(
+                "Advent of Code puzzles only have two parts, got part {}",
+                part
+            ),
+        }
+    }
+}
+
+/// How a freshly computed answer compares to the verified one.
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Correct,
+    Wrong,
+    New,
+}
+
+impl Check {
+    pub fn compare(expected: Option<&str>, answer: &str) -> Check {
+        match expected {
+            Some(expected) if expected == answer => Check::Correct,
+            Some(_) => Check::Wrong,
+            None => Check::New,
+        }
+    }
+}
+
+impl Answers {
+    fn path(year: usize) -> String {
+        format!("answers/{}.toml", year)
+    }
+
+    /// Loads the verified answers of `year`, or an empty set if none are recorded yet.
+    pub fn load(year: usize) -> Answers {
+        match fs::read_to_string(Self::path(year)) {
+            Ok(content) => toml::from_str(&content)
+                .unwrap_or_else(|e| panic!("Could not parse {}: {}", Self::path(year), e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Answers::default(),
+            Err(e) => panic!("Could not read {}: {}", Self::path(year), e),
+        }
+    }
+
+    pub fn save(&self, year: usize) {
+        fs::create_dir_all("answers").unwrap();
+        fs::write(Self::path(year), toml::to_string(self).unwrap()).unwrap();
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<&str> {
+        self.days
+            .get(&Self::key(day))
+            .and_then(|answers| answers.part(part))
+            .map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: usize, part: usize, answer: &str) {
+        *self.days.entry(Self::key(day)).or_default().part_mut(part) = Some(answer.to_owned());
+    }
+
+    fn key(day: usize) -> String {
+        format!("day{:02}", day)
+    }
+}
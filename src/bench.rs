@@ -0,0 +1,184 @@
+use std::{
+    fs::read_to_string,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    days::{Solution, SolutionError},
+    history::{self, Record},
+    parser::MyErr,
+};
+
+/// How many times each phase of a day is run when benchmarking.
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub iterations: usize,
+    /// Stop measuring a phase early once this much time has been spent on it.
+    pub max_time: Duration,
+}
+
+/// Timing statistics over the measured runs of a single phase.
+#[derive(Clone, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        let runs = samples.len();
+        let nanos = samples
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        Stats {
+            runs,
+            mean: Duration::from_nanos(mean as u64),
+            median,
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+            min: samples[0],
+        }
+    }
+}
+
+/// The benchmark results of one day.
+pub struct DayBench {
+    pub parse: Stats,
+    pub parts: [Result<Stats, SolutionError>; 2],
+}
+
+/// Runs `f` for the configured warm-up and measured iterations.
+///
+/// If the first run fails the error is returned without measuring further.
+pub fn measure<T, E>(
+    config: &BenchConfig,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<Stats, E> {
+    let started = Instant::now();
+    let before = Instant::now();
+    black_box(f()?);
+    let mut samples = vec![before.elapsed()];
+
+    for _ in 1..config.warmup {
+        black_box(f().ok());
+    }
+    if config.warmup > 0 {
+        samples.clear();
+    }
+
+    while samples.len() < config.iterations.max(1) {
+        if !samples.is_empty() && started.elapsed() > config.max_time {
+            break;
+        }
+        let before = Instant::now();
+        black_box(f().ok());
+        samples.push(before.elapsed());
+    }
+
+    Ok(Stats::from_samples(samples))
+}
+
+/// Formats a duration with a unit that keeps the number readable.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// A saved baseline to compare fresh measurements against.
+pub struct Comparison {
+    pub baseline: Vec<Record>,
+    /// How many percent slower the median may get before a phase is flagged.
+    pub threshold: f64,
+}
+
+/// Benchmarks each solution on its input and prints a table of the results.
+///
+/// Returns a history record for every phase that could be measured. When a
+/// comparison is given, every phase is compared to its baseline and returns
+/// whether any phase got slower than the threshold allows.
+pub fn bench_days<'a>(
+    solutions: impl Iterator<Item = &'a Solution>,
+    config: &BenchConfig,
+    comparison: Option<&Comparison>,
+) -> (Vec<Record>, bool) {
+    let commit = history::current_commit();
+    let mut records = vec![];
+    let mut slower = false;
+
+    println!(
+        "{:<8} {:<6} {:>10} {:>10} {:>10} {:>10} {:>6}",
+        "Day", "Phase", "Mean", "Median", "Stddev", "Min", "Runs"
+    );
+    for solution in solutions {
+        let day = format!("{} {:>2}", solution.year, solution.day);
+        let bench = read_to_string(solution.input_path())
+            .map_err(MyErr::from)
+            .and_then(|input| (solution.bench)(&input, config));
+        let bench = match bench {
+            Ok(bench) => bench,
+            Err(e) => {
+                println!("{:<8} {}", day, e);
+                continue;
+            }
+        };
+
+        let [part1, part2] = bench.parts;
+        for (phase, stats) in [
+            ("parse", Ok(bench.parse)),
+            ("part 1", part1),
+            ("part 2", part2),
+        ] {
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(e) => {
+                    println!("{:<8} {:<6} {}", day, phase, e);
+                    continue;
+                }
+            };
+            let record = Record::new(&commit, solution.year, solution.day, phase, stats);
+            print!(
+                "{:<8} {:<6} {:>10} {:>10} {:>10} {:>10} {:>6}",
+                day,
+                phase,
+                format_duration(record.stats.mean),
+                format_duration(record.stats.median),
+                format_duration(record.stats.stddev),
+                format_duration(record.stats.min),
+                record.stats.runs
+            );
+            match comparison.map(|c| (c, history::slowdown(&c.baseline, &record))) {
+                Some((comparison, Some(slowdown))) if slowdown > comparison.threshold => {
+                    slower = true;
+                    println!("  {:+.1}% SLOWER", slowdown);
+                }
+                Some((_, Some(slowdown))) => println!("  {:+.1}%", slowdown),
+                Some((_, None)) => println!("  no baseline"),
+                None => println!(),
+            }
+            records.push(record);
+        }
+    }
+
+    (records, slower)
+}
@@ -0,0 +1,618 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{char, multispace1},
+    combinator::{map, opt},
+    multi::separated_list1,
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+use reqwest::{
+    blocking::{Client, RequestBuilder, Response},
+    StatusCode,
+};
+
+use crate::{
+    dirs,
+    helpers::parse_digit,
+    puzzle::strip_tags,
+    release::format_countdown,
+    session::{self, Session},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " by ",
+    env!("CARGO_PKG_AUTHORS")
+);
+
+/// How many times a request is retried when the server has a problem.
+const RETRIES: u32 = 4;
+/// How long to wait before the first retry, doubling for every next one.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How to reach the server and how often it may be bothered.
+pub struct ClientConfig {
+    pub base_url: String,
+    /// Identifies the tool and its author to the server, as Advent of Code asks for.
+    pub user_agent: String,
+    /// The minimum time between two requests, also across invocations.
+    pub min_delay: Duration,
+}
+
+/// Talks to adventofcode.com, or to any server that mimics it at `base_url`.
+///
+/// Requests are spaced out by the configured minimum delay, and requests for pages
+/// the server fails with a 5xx status are retried with exponential backoff.
+pub struct AocClient {
+    base_url: String,
+    session: Session,
+    http: Client,
+    min_delay: Duration,
+    /// Where the time of the last request is kept, so other invocations wait their turn too.
+    last_request: Option<PathBuf>,
+    backoff: Duration,
+    /// Held while waiting for a turn, so concurrent requests are spaced out too.
+    throttle: Mutex<()>,
+}
+
+impl AocClient {
+    /// Creates a client authenticated with the session cookie found by `session::find`.
+    pub fn new(config: &ClientConfig) -> Result<AocClient, ClientError> {
+        let session = session::find().ok_or(ClientError::MissingSession)?;
+        AocClient::with_session(config, session)
+    }
+
+    fn with_session(config: &ClientConfig, session: Session) -> Result<AocClient, ClientError> {
+        Ok(AocClient {
+            base_url: config.base_url.trim_end_matches('/').to_owned(),
+            session,
+            http: Client::builder().user_agent(&config.user_agent).build()?,
+            min_delay: config.min_delay,
+            last_request: last_request_path(),
+            backoff: INITIAL_BACKOFF,
+            throttle: Mutex::new(()),
+        })
+    }
+
+    /// Sends a request once it is our turn, retrying it while the server has problems.
+    ///
+    /// Only for requests that can safely be repeated, see `send_once`.
+    fn send(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        let mut backoff = self.backoff;
+        for _ in 0..RETRIES {
+            let response = self.send_once(request.try_clone().unwrap())?;
+            if !response.status().is_server_error() {
+                return Ok(response);
+            }
+            thread::sleep(backoff);
+            backoff *= 2;
+        }
+        self.send_once(request)
+    }
+
+    /// Sends a request once it is our turn, without retrying it.
+    ///
+    /// A failed answer may still have been recorded, and sending it again could
+    /// count as another wrong answer.
+    fn send_once(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        self.wait_for_turn();
+        let request = request.header("cookie", format!("session={};", self.session.cookie));
+        Ok(request.send()?)
+    }
+
+    /// Sleeps until `min_delay` has passed since the last request, and records this one.
+    fn wait_for_turn(&self) {
+        let _turn = self.throttle.lock().unwrap();
+        let path = self.last_request.as_ref();
+        let last = path
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(since) = last.and_then(|last| SystemTime::now().duration_since(last).ok()) {
+            thread::sleep(self.min_delay.saturating_sub(since));
+        }
+
+        if let Some(path) = path {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            // Throttling is best effort, so failing to record the request is fine
+            let _ = fs::create_dir_all(path.parent().unwrap())
+                .and_then(|_| fs::write(path, now.as_millis().to_string()));
+        }
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Fetches the front page and returns the name of the user the session belongs to.
+    pub fn user(&self) -> Result<String, ClientError> {
+        let response = self.send(self.http.get(format!("{}/", self.base_url)))?;
+        if !response.status().is_success() {
+            return Err(ClientError::Status(response.status()));
+        }
+        parse_user(&response.text()?).ok_or(ClientError::NotLoggedIn)
+    }
+
+    /// Fetches the puzzle input of a day, without the trailing newline.
+    pub fn input(&self, year: usize, day: usize) -> Result<String, ClientError> {
+        let response = self.send(
+            self.http
+                .get(format!("{}/{}/day/{}/input", self.base_url, year, day)),
+        )?;
+
+        let status = response.status();
+        let body = response.text()?;
+        if !status.is_success() {
+            // An error page only says more than the status when it asks to log in or slow down
+            return Err(match check_input(&body) {
+                Err(e @ (ClientError::NotLoggedIn | ClientError::TooManyRequests)) => e,
+                _ => ClientError::Status(status),
+            });
+        }
+        check_input(&body)?;
+
+        Ok(body.strip_suffix('\n').unwrap_or(&body).to_owned())
+    }
+
+    /// Fetches the page of a day's puzzle, which includes part 2 once part 1 is solved.
+    pub fn puzzle(&self, year: usize, day: usize) -> Result<String, ClientError> {
+        let response = self.send(
+            self.http
+                .get(format!("{}/{}/day/{}", self.base_url, year, day)),
+        )?;
+        if !response.status().is_success() {
+            return Err(ClientError::Status(response.status()));
+        }
+        Ok(response.text()?)
+    }
+
+    /// Fetches the JSON of a private leaderboard.
+    pub fn leaderboard(&self, year: usize, id: &str) -> Result<String, ClientError> {
+        let response = self.send(self.http.get(format!(
+            "{}/{}/leaderboard/private/view/{}.json",
+            self.base_url, year, id
+        )))?;
+        if !response.status().is_success() {
+            return Err(ClientError::Status(response.status()));
+        }
+        Ok(response.text()?)
+    }
+
+    /// Posts the answer to a part and classifies the page the server responds with.
+    pub fn submit(
+        &self,
+        year: usize,
+        day: usize,
+        part: usize,
+        answer: &str,
+    ) -> Result<SubmitOutcome, ClientError> {
+        let response = self.send_once(
+            self.http
+                .post(format!("{}/{}/day/{}/answer", self.base_url, year, day))
+                .form(&[("level", part.to_string()), ("answer", answer.to_owned())]),
+        )?;
+        if !response.status().is_success() {
+            return Err(ClientError::Status(response.status()));
+        }
+        Ok(SubmitOutcome::from_html(&response.text()?))
+    }
+}
+
+/// Why talking to the server failed.
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session cookie was found in any of the places it may be kept.
+    MissingSession,
+    Http(reqwest::Error),
+    Status(StatusCode),
+    /// The server answered with a page asking to log in, usually because the session expired.
+    NotLoggedIn,
+    /// The server asked us to stop requesting the same thing over and over.
+    TooManyRequests,
+    /// The server answered with an HTML page instead of the plain text input.
+    Html,
+    EmptyInput,
+    /// The input could not be written to disk.
+    File(io::Error),
+    /// The puzzle hasn't unlocked yet and will in this long.
+    Locked(Duration),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "No session cookie found. Set AOC_SESSION or put it in .session or ~/.config/aoc/session"
+            ),
+            ClientError::Http(e) => write!(f, "Request failed: {}", e),
+            ClientError::Status(status) => write!(f, "Server responded with {}", status),
+            ClientError::NotLoggedIn => write!(
+                f,
+                "Not logged in. Is your session cookie correct and not expired?"
+            ),
+            ClientError::TooManyRequests => write!(
+                f,
+                "The server asked not to repeatedly request this input, try again later"
+            ),
+            ClientError::Html => write!(f, "Server responded with a web page instead of an input"),
+            ClientError::EmptyInput => write!(f, "Server responded with an empty input"),
+            ClientError::File(e) => write!(f, "Could not save input: {}", e),
+            ClientError::Locked(wait) => {
+                write!(f, "The puzzle unlocks in {}", format_countdown(*wait))
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> ClientError {
+        ClientError::Http(e)
+    }
+}
+
+/// Rejects bodies that are an error message or a web page rather than a puzzle input.
+fn check_input(body: &str) -> Result<(), ClientError> {
+    let start = body.trim_start().to_lowercase();
+    if body.contains("Please log in") || body.contains("Puzzle inputs differ by user") {
+        Err(ClientError::NotLoggedIn)
+    } else if body.contains("Please don't repeatedly request this endpoint") {
+        Err(ClientError::TooManyRequests)
+    } else if start.starts_with("<!doctype") || start.starts_with("<html") {
+        Err(ClientError::Html)
+    } else if body.trim().is_empty() {
+        Err(ClientError::EmptyInput)
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Another answer was given too recently, with the time left to wait if the page says.
+    RateLimited(Option<Duration>),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl SubmitOutcome {
+    /// Classifies the `<article>` of the page returned after posting an answer.
+    pub fn from_html(html: &str) -> SubmitOutcome {
+        let article = html
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(html, |(article, _)| article);
+
+        if article.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if article.contains("your answer is too high") {
+            SubmitOutcome::TooHigh
+        } else if article.contains("your answer is too low") {
+            SubmitOutcome::TooLow
+        } else if article.contains("That's not the right answer") {
+            SubmitOutcome::Wrong
+        } else if article.contains("You gave an answer too recently") {
+            let wait = article
+                .match_indices("ou have ")
+                .find_map(|(start, _)| parse_wait(&article[start..]).ok())
+                .map(|(_, wait)| wait);
+            SubmitOutcome::RateLimited(wait)
+        } else if article.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::AlreadySolved
+        } else {
+            SubmitOutcome::Unknown(plain_text(article))
+        }
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct"),
+            SubmitOutcome::TooHigh => write!(f, "too high"),
+            SubmitOutcome::TooLow => write!(f, "too low"),
+            SubmitOutcome::Wrong => write!(f, "wrong"),
+            SubmitOutcome::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            SubmitOutcome::RateLimited(None) => write!(f, "rate limited"),
+            SubmitOutcome::AlreadySolved => write!(f, "already solved"),
+            SubmitOutcome::Unknown(message) => write!(f, "unknown response: {}", message),
+        }
+    }
+}
+
+/// Parses the wait time in "ou have 1m 23s left to wait".
+fn parse_wait(input: &str) -> IResult<&str, Duration> {
+    preceded(
+        tag("ou have "),
+        terminated(
+            map(
+                separated_list1(
+                    multispace1,
+                    tuple((
+                        parse_digit("wait time"),
+                        opt(char('h')),
+                        opt(char('m')),
+                        opt(char('s')),
+                    )),
+                ),
+                |parts: Vec<(u64, _, _, _)>| {
+                    parts
+                        .into_iter()
+                        .map(|(n, hours, minutes, _)| {
+                            if hours.is_some() {
+                                n * 3600
+                            } else if minutes.is_some() {
+                                n * 60
+                            } else {
+                                n
+                            }
+                        })
+                        .map(Duration::from_secs)
+                        .sum()
+                },
+            ),
+            take_until("left to wait"),
+        ),
+    )(input)
+}
+
+/// Where the time of the last request is kept.
+fn last_request_path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("last_request"))
+}
+
+/// Finds the user name in the `<div class="user">` of a logged in page.
+fn parse_user(html: &str) -> Option<String> {
+    let (_, rest) = html.split_once("<div class=\"user\">")?;
+    let (user, _) = rest.split_once("</div>")?;
+    let user = plain_text(user.split("<span").next()?);
+    (!user.is_empty()).then_some(user)
+}
+
+/// The text of `html` on a single line.
+fn plain_text(html: &str) -> String {
+    strip_tags(html)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    };
+
+    use super::{AocClient, ClientConfig, ClientError, SubmitOutcome};
+    use crate::session::{Session, Source};
+
+    /// A stand-in for the server that answers one request with each of `responses`, in order.
+    ///
+    /// Returns its base URL, and a handle that joins to the requests it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request = String::new();
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                length = value.trim().parse().unwrap();
+                            }
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    let mut body_read = vec![0; length];
+                    reader.read_exact(&mut body_read).unwrap();
+                    request.push_str(&String::from_utf8(body_read).unwrap());
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} Stand-in\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+        (base_url, server)
+    }
+
+    /// A client for the stand-in server at `base_url` that neither waits nor records its requests.
+    fn client(base_url: &str) -> AocClient {
+        let config = ClientConfig {
+            base_url: base_url.to_owned(),
+            user_agent: "aoc-tests".to_owned(),
+            min_delay: Duration::ZERO,
+        };
+        let session = Session {
+            cookie: "test-cookie".to_owned(),
+            source: Source::Env,
+        };
+        let mut client = AocClient::with_session(&config, session).unwrap();
+        client.last_request = None;
+        client
+    }
+
+    #[test]
+    fn submits_answers_as_a_form_with_the_session() {
+        let (base_url, server) = serve(vec![(
+            200,
+            include_str!("../fixtures/answer-too-high.html"),
+        )]);
+        assert_eq!(
+            client(&base_url).submit(2023, 9, 1, "1234").unwrap(),
+            SubmitOutcome::TooHigh
+        );
+
+        let requests = server.join().unwrap();
+        let request = requests[0].to_lowercase();
+        assert!(request.starts_with("post /2023/day/9/answer http/1.1\r\n"));
+        assert!(request.contains("\r\ncookie: session=test-cookie;\r\n"));
+        assert!(request.contains("\r\nuser-agent: aoc-tests\r\n"));
+        assert!(request.contains("\r\ncontent-type: application/x-www-form-urlencoded\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=1234"));
+    }
+
+    #[test]
+    fn does_not_resubmit_when_the_server_fails() {
+        let (base_url, server) = serve(vec![(500, "")]);
+        assert!(matches!(
+            client(&base_url).submit(2023, 9, 2, "1234"),
+            Err(ClientError::Status(status)) if status == 500
+        ));
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn finds_the_logged_in_user() {
+        let (base_url, server) = serve(vec![
+            (
+                200,
+                r#"<header><div class="user">Jane Doe <span class="star-count">34*</span></div></header>"#,
+            ),
+            (200, "<header><nav>[Log In]</nav></header>"),
+        ]);
+        let client = client(&base_url);
+        assert_eq!(client.user().unwrap(), "Jane Doe");
+        assert!(matches!(client.user(), Err(ClientError::NotLoggedIn)));
+        assert!(server.join().unwrap()[0].starts_with("GET / HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn retries_server_errors_with_backoff() {
+        let (base_url, server) = serve(vec![(503, ""), (502, ""), (200, "0 3 6\n")]);
+        let mut client = client(&base_url);
+        client.backoff = Duration::from_millis(50);
+        let started = Instant::now();
+        assert_eq!(client.input(2023, 9).unwrap(), "0 3 6");
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn gives_up_when_the_server_keeps_failing() {
+        let (base_url, server) = serve(vec![(503, ""); 5]);
+        let mut client = client(&base_url);
+        client.backoff = Duration::from_millis(1);
+        assert!(matches!(
+            client.input(2023, 9),
+            Err(ClientError::Status(status)) if status == 503
+        ));
+        assert_eq!(server.join().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn waits_for_the_last_request_of_other_clients() {
+        let last_request = env::temp_dir().join(format!("aoc-last-request-{}", std::process::id()));
+        let _ = fs::remove_file(&last_request);
+        let (base_url, server) = serve(vec![(200, "1\n"), (200, "2\n")]);
+        let throttled = || {
+            let mut client = client(&base_url);
+            client.min_delay = Duration::from_millis(300);
+            client.last_request = Some(last_request.clone());
+            client
+        };
+
+        let started = Instant::now();
+        assert_eq!(throttled().input(2023, 1).unwrap(), "1");
+        assert!(started.elapsed() < Duration::from_millis(300));
+        // A new client, like the next invocation, only knows about the first request from disk
+        assert_eq!(throttled().input(2023, 2).unwrap(), "2");
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        assert_eq!(server.join().unwrap().len(), 2);
+        fs::remove_file(&last_request).unwrap();
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (base_url, server) = serve(vec![(200, "0 3 6\n1 3 5\n")]);
+        assert_eq!(client(&base_url).input(2023, 9).unwrap(), "0 3 6\n1 3 5");
+        assert!(server.join().unwrap()[0].starts_with("GET /2023/day/9/input HTTP/1.1\r\n"));
+    }
+
+    fn outcome(page: &str) -> SubmitOutcome {
+        SubmitOutcome::from_html(page)
+    }
+
+    #[test]
+    fn recognizes_answers() {
+        assert_eq!(
+            outcome(include_str!("../fixtures/answer-correct.html")),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            outcome(include_str!("../fixtures/answer-wrong.html")),
+            SubmitOutcome::Wrong
+        );
+        assert_eq!(
+            outcome(include_str!("../fixtures/answer-wrong-level.html")),
+            SubmitOutcome::AlreadySolved
+        );
+    }
+
+    #[test]
+    fn tells_whether_a_wrong_answer_is_too_high_or_too_low() {
+        assert_eq!(
+            outcome(include_str!("../fixtures/answer-too-high.html")),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            outcome(include_str!("../fixtures/answer-too-low.html")),
+            SubmitOutcome::TooLow
+        );
+    }
+
+    #[test]
+    fn reads_how_long_to_wait() {
+        let page = include_str!("../fixtures/answer-too-recent.html");
+        assert_eq!(
+            outcome(page),
+            SubmitOutcome::RateLimited(Some(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            outcome(&page.replace("1m 23s", "2h 5s")),
+            SubmitOutcome::RateLimited(Some(Duration::from_secs(7205)))
+        );
+        assert_eq!(
+            outcome(&page.replace("You have 1m 23s left to wait.", "")),
+            SubmitOutcome::RateLimited(None)
+        );
+    }
+}
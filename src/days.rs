@@ -0,0 +1,244 @@
+use crate::bench::{measure, BenchConfig, DayBench};
+use crate::parser::{MyErr, ParseResult};
+use std::fmt::Display;
+use std::fs::read_to_string;
+use std::time::{Duration, Instant};
+
+/// Declares the day modules of a year and registers each day's solution in `SOLUTIONS`.
+macro_rules! days {
+    (year = $year:literal; $($module:ident::$day:ident = $number:literal,)*) => {
+        $(pub mod $module;)*
+
+        pub const SOLUTIONS: &[$crate::days::Solution] = &[
+            $($crate::days::Solution {
+                year: $year,
+                day: $number,
+                name: stringify!($day),
+                solve: <$module::$day as $crate::days::Day>::solve,
+                answer: <$module::$day as $crate::days::Day>::answer,
+                bench: <$module::$day as $crate::days::Day>::bench,
+                examples: <$module::$day as $crate::days::Day>::EXAMPLES,
+            },)*
+        ];
+    };
+}
+pub(crate) use days;
+
+/// Declares the example inputs of a day in `EXAMPLES` and generates a test for each of them.
+///
+/// Example files are looked up relative to the day's source file.
+macro_rules! examples {
+    (
+        $day:ident {
+            $($name:ident: $file:literal => {
+                $(part_1: $part_1:literal)? $(,)?
+                $(part_2: $part_2:literal)? $(,)?
+            },)*
+        }
+    ) => {
+        const EXAMPLES: &[$crate::days::Example] = &[
+            $($crate::days::Example {
+                name: stringify!($name),
+                input: include_str!($file),
+                part_1: $crate::days::examples!(@expected $($part_1)?),
+                part_2: $crate::days::examples!(@expected $($part_2)?),
+            },)*
+        ];
+
+        #[cfg(test)]
+        mod examples {
+            $(#[test]
+            fn $name() {
+                $crate::days::check_example::<super::$day>(stringify!($name));
+            })*
+        }
+    };
+    (@expected) => { None };
+    (@expected $expected:literal) => { Some($expected) };
+}
+pub(crate) use examples;
+
+/// A registered solution for a single day of a single year.
+pub struct Solution {
+    pub year: usize,
+    pub day: usize,
+    pub name: &'static str,
+    pub solve: fn(&str, &[usize]) -> Result<DayResult, MyErr>,
+    pub answer: fn(&str, usize) -> Result<String, MyErr>,
+    pub examples: &'static [Example],
+    pub bench: fn(&str, &BenchConfig) -> Result<DayBench, MyErr>,
+}
+
+impl Solution {
+    pub fn input_path(&self) -> String {
+        format!("inputs/{}/day{:02}.txt", self.year, self.day)
+    }
+}
+
+/// A named example input from the puzzle description with the answers it should produce.
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part_1: Option<&'static str>,
+    pub part_2: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: usize) -> Option<&'static str> {
+        match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => None,
+        }
+    }
+
+    /// The parts with an expected answer, as the other parts may not work on this example.
+    pub fn parts(&self) -> Vec<usize> {
+        (1..=2)
+            .filter(|&part| self.expected(part).is_some())
+            .collect()
+    }
+}
+
+/// The answers to the parts of a day that were run together with how long parsing took.
+pub struct DayResult {
+    pub parse: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+/// The answer to one part together with how long it took to compute.
+pub struct PartResult {
+    pub part: usize,
+    pub answer: Result<String, SolutionError>,
+    pub duration: Duration,
+}
+
+/// Why a part could not produce an answer.
+#[derive(Debug)]
+pub enum SolutionError {
+    /// The part has not been implemented yet.
+    Unsolved,
+    /// The input does not have the shape the solution expects.
+    InvalidInput(String),
+    /// The solution ran to completion without finding an answer.
+    NoAnswer,
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolutionError::Unsolved => write!(f, "not solved yet"),
+            SolutionError::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+            SolutionError::NoAnswer => write!(f, "no answer found"),
+        }
+    }
+}
+
+pub trait Day {
+    type Input;
+
+    fn parse(input_string: &str) -> ParseResult<'_, Self::Input>;
+
+    type Output1: Display;
+
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError>;
+
+    type Output2: Display;
+
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError>;
+
+    /// Example inputs from the puzzle description, declared with `examples!`.
+    const EXAMPLES: &'static [Example] = &[];
+
+    fn parse_file(fp: &str) -> Result<Self::Input, MyErr> {
+        let input_string = read_to_string(fp)?;
+        Self::parse_input(&input_string)
+    }
+
+    fn parse_input(input_string: &str) -> Result<Self::Input, MyErr> {
+        let (remaining, input) =
+            Self::parse(input_string).map_err(|e| MyErr::parse(input_string, e))?;
+        // A parser that gives up halfway would otherwise silently solve part of the input
+        if !remaining.trim().is_empty() {
+            return Err(MyErr::trailing(input_string, remaining));
+        }
+        Ok(input)
+    }
+
+    /// Computes the answer to a single part of the parsed input.
+    fn solve_part(input: &Self::Input, part: usize) -> Result<String, SolutionError> {
+        match part {
+            1 => Self::part_1(input).map(|answer| answer.to_string()),
+            2 => Self::part_2(input).map(|answer| answer.to_string()),
+            part => panic!(
+                "Advent of Code puzzles only have two parts, got part {}",
+                part
+            ),
+        }
+    }
+
+    /// Parses the input file and computes the answer to a single part.
+    fn answer(fp: &str, part: usize) -> Result<String, MyErr> {
+        let input = Self::parse_file(fp)?;
+        Ok(Self::solve_part(&input, part)?)
+    }
+
+    /// Parses the input and computes the answers to `parts`.
+    fn solve(input_string: &str, parts: &[usize]) -> Result<DayResult, MyErr> {
+        let before_parse = Instant::now();
+        let input = Self::parse_input(input_string)?;
+        let parse = before_parse.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let before = Instant::now();
+                let answer = Self::solve_part(&input, part);
+                PartResult {
+                    part,
+                    answer,
+                    duration: before.elapsed(),
+                }
+            })
+            .collect();
+
+        Ok(DayResult { parse, parts })
+    }
+
+    /// Measures parsing and both parts separately over repeated runs.
+    fn bench(input_string: &str, config: &BenchConfig) -> Result<DayBench, MyErr> {
+        let parse = measure(config, || Self::parse_input(input_string))?;
+        let input = Self::parse_input(input_string)?;
+        let part1 = measure(config, || Self::part_1(&input));
+        let part2 = measure(config, || Self::part_2(&input));
+
+        Ok(DayBench {
+            parse,
+            parts: [part1, part2],
+        })
+    }
+}
+
+/// Solves the example called `name` and asserts that it produces the expected answers.
+///
+/// Only the parts with an expected answer are run.
+#[cfg(test)]
+pub fn check_example<D: Day>(name: &str) {
+    let example = D::EXAMPLES
+        .iter()
+        .find(|example| example.name == name)
+        .unwrap_or_else(|| panic!("No example called {}", name));
+    let input = D::parse_input(example.input).unwrap_or_else(|e| panic!("{}", e));
+
+    for part in example.parts() {
+        match D::solve_part(&input, part) {
+            Ok(answer) => assert_eq!(
+                Some(answer.as_str()),
+                example.expected(part),
+                "part {}",
+                part
+            ),
+            Err(e) => panic!("part {} failed: {}", part, e),
+        }
+    }
+}
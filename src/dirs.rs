@@ -0,0 +1,21 @@
+use std::{env, path::PathBuf};
+
+/// `aoc` in the user's config directory.
+pub fn config_dir() -> Option<PathBuf> {
+    app_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `aoc` in the user's cache directory.
+pub fn cache_dir() -> Option<PathBuf> {
+    app_dir("XDG_CACHE_HOME", ".cache")
+}
+
+/// `aoc` in the directory `var` points to, or else in `fallback` in the home directory,
+/// following the XDG base directory spec.
+fn app_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    let dir = env::var_os(var)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(fallback)))?;
+    Some(dir.join("aoc"))
+}
@@ -1,38 +1,220 @@
-use std::{fmt::Debug, ops::RangeFrom};
-use std::{ops::RangeTo, str::FromStr};
+use std::ops::{RangeFrom, RangeTo};
+use std::str::FromStr;
 
 use nom::{
-    character::complete::{char, digit1},
-    combinator::{map, recognize},
-    sequence::preceded,
-    AsChar, IResult, InputIter, InputTakeAtPosition, Offset, ParseTo, Slice,
+    branch::alt,
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{cut, eof, map_opt, not, opt, recognize, value, verify},
+    error::{context, ContextError, ParseError, VerboseError},
+    multi::many1,
+    sequence::{pair, separated_pair},
+    AsChar, Err, IResult, InputIter, InputLength, InputTakeAtPosition, Offset, ParseTo, Parser,
+    Slice,
 };
 
-pub fn parse_digit<I, T>(error_message: &'static str) -> impl FnMut(I) -> IResult<I, T>
+use crate::parser::ParseResult;
+
+/// Parses an unsigned number into any integer type, failing with `error_message` as
+/// context when it doesn't fit.
+pub fn parse_digit<I, T, E>(error_message: &'static str) -> impl FnMut(I) -> IResult<I, T, E>
 where
-    I: Slice<RangeFrom<usize>> + InputIter + ParseTo<T> + InputTakeAtPosition,
-    <I as InputIter>::Item: AsChar,
+    I: Clone + InputTakeAtPosition + ParseTo<T>,
     <I as InputTakeAtPosition>::Item: AsChar,
     T: FromStr,
-    T::Err: Debug,
+    E: ParseError<I> + ContextError<I>,
 {
-    map(digit1, |n: I| n.parse_to().expect(error_message))
+    context(error_message, map_opt(digit1, |n: I| n.parse_to()))
 }
-pub fn parse_ndigit<I, T>(error_message: &'static str) -> impl FnMut(I) -> IResult<I, T>
+
+/// Parses a number with an optional `+` or `-` sign into any integer type, failing with
+/// `error_message` as context when it doesn't fit, or when it is negative and `T` is unsigned.
+pub fn parse_ndigit<I, T, E>(error_message: &'static str) -> impl FnMut(I) -> IResult<I, T, E>
 where
-    I: Slice<RangeFrom<usize>>
+    I: Clone
+        + Offset
+        + InputLength
         + InputIter
-        + ParseTo<T>
         + InputTakeAtPosition
-        + Clone
-        + Offset
-        + Slice<RangeTo<usize>>,
+        + Slice<RangeFrom<usize>>
+        + Slice<RangeTo<usize>>
+        + ParseTo<T>,
     <I as InputIter>::Item: AsChar,
     <I as InputTakeAtPosition>::Item: AsChar,
     T: FromStr,
-    T::Err: Debug,
+    E: ParseError<I> + ContextError<I>,
 {
-    map(recognize(preceded(char('-'), digit1)), |n: I| {
-        n.parse_to().expect(error_message)
-    })
+    context(
+        error_message,
+        map_opt(
+            recognize(pair(opt(alt((char('+'), char('-')))), digit1)),
+            |n: I| n.parse_to(),
+        ),
+    )
+}
+
+/// Numbers on a single line separated by spaces, like `41 48  83 -86`.
+pub fn number_list<'a, T: FromStr>(
+    error_message: &'static str,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<T>> {
+    list_of(space1, one_of("+-0123456789"), parse_ndigit(error_message))
+}
+
+/// A `label: value` pair, returning both.
+///
+/// Any spaces after the colon are skipped, so `Time:      7  15` parses with `number_list`.
+pub fn labelled<'a, L, V>(
+    label: impl Parser<&'a str, L, VerboseError<&'a str>>,
+    value: impl Parser<&'a str, V, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, (L, V)> {
+    separated_pair(label, pair(char(':'), space0), value)
+}
+
+/// One `line` per line, with either `\n` or `\r\n` line endings.
+pub fn lines_of<'a, O>(
+    line: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>> {
+    list_of(
+        line_ending,
+        not(pair(space0, alt((line_ending, eof)))),
+        line,
+    )
+}
+
+/// Rows of `cell`s, one row per line, which all have to be equally long.
+pub fn grid_of<'a, O>(
+    cell: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<Vec<O>>> {
+    context(
+        "grid",
+        verify(lines_of(many1(cell)), |rows: &Vec<Vec<O>>| {
+            rows.iter().all(|row| row.len() == rows[0].len())
+        }),
+    )
+}
+
+/// The end of a line followed by one or more blank lines, which separates blocks of lines.
+///
+/// Blocks usually differ in shape, so they are parsed one after another with this in between.
+pub fn blank_lines(input: &str) -> ParseResult<'_, ()> {
+    value((), pair(line_ending, many1(line_ending)))(input)
+}
+
+/// One or more `element`s separated by `separator`, like `separated_list1`.
+///
+/// The list only ends where `starts` doesn't match after a separator. Past that point
+/// the element is `cut`, so an element that fails to parse, like a number that doesn't
+/// fit, is reported with its context instead of quietly ending the list.
+fn list_of<'a, O, S, T>(
+    mut separator: impl Parser<&'a str, S, VerboseError<&'a str>>,
+    mut starts: impl Parser<&'a str, T, VerboseError<&'a str>>,
+    mut element: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>> {
+    move |input| {
+        let (mut input, first) = element.parse(input)?;
+        let mut elements = vec![first];
+        loop {
+            let next = match separator.parse(input) {
+                Ok((next, _)) if starts.parse(next).is_ok() => next,
+                Ok(_) | Err(Err::Error(_)) => return Ok((input, elements)),
+                Err(e) => return Err(e),
+            };
+            let (next, element) = cut(|next| element.parse(next))(next)?;
+            elements.push(element);
+            input = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        character::complete::{alpha1, one_of},
+        error::{Error, VerboseErrorKind},
+        sequence::tuple,
+        Err,
+    };
+
+    use super::{blank_lines, grid_of, labelled, lines_of, number_list, parse_digit, parse_ndigit};
+
+    #[test]
+    fn parses_numbers_of_any_size() {
+        assert_eq!(
+            parse_digit::<_, u128, Error<_>>("n")("340282366920938463463374607431768211455 rest"),
+            Ok((" rest", u128::MAX))
+        );
+        assert_eq!(
+            parse_ndigit::<_, i128, Error<_>>("n")("-170141183460469231731687303715884105728"),
+            Ok(("", i128::MIN))
+        );
+        assert_eq!(parse_ndigit::<_, i8, Error<_>>("n")("+12"), Ok(("", 12)));
+    }
+
+    #[test]
+    fn fails_on_numbers_that_do_not_fit() {
+        assert!(parse_digit::<_, u8, Error<_>>("n")("256").is_err());
+        assert!(parse_ndigit::<_, i8, Error<_>>("n")("-129").is_err());
+        assert!(parse_ndigit::<_, u32, Error<_>>("n")("-1").is_err());
+        assert!(parse_ndigit::<_, i32, Error<_>>("n")("-").is_err());
+    }
+
+    #[test]
+    fn parses_labelled_number_lists() {
+        assert_eq!(
+            labelled(alpha1, number_list::<i32>("n"))("Time:      7  15 -30\n"),
+            Ok(("\n", ("Time", vec![7, 15, -30])))
+        );
+    }
+
+    #[test]
+    fn parses_lines_with_either_line_ending() {
+        let expected = vec![vec![1, 2], vec![3], vec![4, 5]];
+        for input in ["1 2\n3\n4 5\n", "1 2\r\n3\r\n4 5\r\n"] {
+            let (remaining, lines) = lines_of(number_list::<u8>("n"))(input).unwrap();
+            assert_eq!(lines, expected);
+            assert_eq!(remaining.trim(), "");
+        }
+    }
+
+    #[test]
+    fn ends_lists_where_the_next_element_does_not_start() {
+        assert_eq!(number_list::<u8>("n")("1 2 | 3"), Ok((" | 3", vec![1, 2])));
+        assert_eq!(
+            lines_of(number_list::<u8>("n"))("1 2\n3\n  \n"),
+            Ok(("\n  \n", vec![vec![1, 2], vec![3]]))
+        );
+    }
+
+    #[test]
+    fn reports_the_context_of_list_elements_that_fail() {
+        let Err(Err::Failure(e)) =
+            lines_of(number_list::<isize>("num"))("0 3 6\n1 3 5 99999999999999999999999\n")
+        else {
+            panic!("an overflowing number should fail the list");
+        };
+        assert_eq!(e.errors[0].0, "99999999999999999999999\n");
+        assert!(e.errors.contains(&(
+            "99999999999999999999999\n",
+            VerboseErrorKind::Context("num")
+        )));
+    }
+
+    #[test]
+    fn parses_grids() {
+        assert_eq!(
+            grid_of(one_of("#."))("#.\r\n.#"),
+            Ok(("", vec![vec!['#', '.'], vec!['.', '#']]))
+        );
+        assert!(grid_of(one_of("#."))("#.\n.").is_err());
+    }
+
+    #[test]
+    fn parses_blocks_separated_by_blank_lines() {
+        for input in ["1\n2\n\n3\n\n\n4", "1\r\n2\r\n\r\n3\r\n\r\n\r\n4"] {
+            let block = || lines_of(parse_digit::<_, u8, _>("n"));
+            assert_eq!(
+                tuple((block(), blank_lines, block(), blank_lines, block()))(input),
+                Ok(("", (vec![1, 2], (), vec![3], (), vec![4])))
+            );
+        }
+    }
 }
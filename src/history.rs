@@ -0,0 +1,231 @@
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    process::Command,
+    time::Duration,
+};
+
+use chrono::Local;
+
+use crate::bench::Stats;
+
+const HISTORY_PATH: &str = "bench/history.csv";
+const HEADER: &str = "timestamp,commit,year,day,phase,runs,mean_ns,median_ns,stddev_ns,min_ns";
+
+/// One measured phase of one day, as stored in the benchmark history.
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub timestamp: String,
+    pub commit: String,
+    pub year: usize,
+    pub day: usize,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+impl Record {
+    pub fn new(commit: &str, year: usize, day: usize, phase: &str, stats: Stats) -> Record {
+        Record {
+            timestamp: Local::now().to_rfc3339(),
+            commit: commit.to_owned(),
+            year,
+            day,
+            phase: phase.to_owned(),
+            stats,
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            self.timestamp,
+            self.commit,
+            self.year,
+            self.day,
+            self.phase,
+            self.stats.runs,
+            self.stats.mean.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.stddev.as_nanos(),
+            self.stats.min.as_nanos()
+        )
+    }
+
+    fn from_csv(line: &str) -> Option<Record> {
+        let fields = line.split(',').collect::<Vec<_>>();
+        let [timestamp, commit, year, day, phase, runs, mean, median, stddev, min] = fields[..]
+        else {
+            return None;
+        };
+        let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+        Some(Record {
+            timestamp: timestamp.to_owned(),
+            commit: commit.to_owned(),
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            phase: phase.to_owned(),
+            stats: Stats {
+                runs: runs.parse().ok()?,
+                mean: nanos(mean)?,
+                median: nanos(median)?,
+                stddev: nanos(stddev)?,
+                min: nanos(min)?,
+            },
+        })
+    }
+
+    fn same_phase(&self, other: &Record) -> bool {
+        self.year == other.year && self.day == other.day && self.phase == other.phase
+    }
+}
+
+/// The short hash of the checked out commit, marked `-dirty` if there are uncommitted changes.
+///
+/// Untracked files and the history itself don't count, as they don't change what is measured.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+
+    let exclude_history = format!(":(exclude){}", HISTORY_PATH);
+    let status = [
+        "status",
+        "--porcelain",
+        "--untracked-files=no",
+        "--",
+        ".",
+        &exclude_history,
+    ];
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) if git(&status).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", commit)
+        }
+        Some(commit) => commit,
+        None => "unknown".to_owned(),
+    }
+}
+
+/// Loads every record in the benchmark history, oldest first.
+pub fn load() -> Vec<Record> {
+    match fs::read_to_string(HISTORY_PATH) {
+        Ok(content) => content
+            .lines()
+            .skip(1)
+            .filter_map(Record::from_csv)
+            .collect(),
+        Err(e) if e.kind() == ErrorKind::NotFound => vec![],
+        Err(e) => panic!("Could not read {}: {}", HISTORY_PATH, e),
+    }
+}
+
+/// Appends `records` to the benchmark history.
+pub fn append(records: &[Record]) {
+    fs::create_dir_all("bench").unwrap();
+    let exists = fs::metadata(HISTORY_PATH).is_ok();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)
+        .unwrap();
+    if !exists {
+        writeln!(file, "{}", HEADER).unwrap();
+    }
+    for record in records {
+        writeln!(file, "{}", record.to_csv()).unwrap();
+    }
+}
+
+/// The latest record of each phase measured at a commit starting with `commit`.
+pub fn baseline(history: Vec<Record>, commit: &str) -> Vec<Record> {
+    let mut baseline: Vec<Record> = vec![];
+    for record in history
+        .into_iter()
+        .filter(|record| record.commit.starts_with(commit))
+    {
+        baseline.retain(|existing| !existing.same_phase(&record));
+        baseline.push(record);
+    }
+    baseline
+}
+
+/// How much slower `record` is than the matching phase in `baseline`, in percent of the median.
+pub fn slowdown(baseline: &[Record], record: &Record) -> Option<f64> {
+    baseline
+        .iter()
+        .find(|existing| existing.same_phase(record))
+        .map(|existing| {
+            let before = existing.stats.median.as_nanos() as f64;
+            let after = record.stats.median.as_nanos() as f64;
+            (after - before) / before.max(1.0) * 100.0
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{baseline, slowdown, Record};
+    use crate::bench::Stats;
+
+    fn record(commit: &str, day: usize, phase: &str, median_ns: u64) -> Record {
+        let stats = Stats {
+            runs: 10,
+            mean: Duration::from_nanos(median_ns + 5),
+            median: Duration::from_nanos(median_ns),
+            stddev: Duration::from_nanos(3),
+            min: Duration::from_nanos(median_ns - 5),
+        };
+        Record::new(commit, 2023, day, phase, stats)
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let written = record("abc1234-dirty", 5, "part1", 1500);
+        let read = Record::from_csv(&written.to_csv()).unwrap();
+        assert_eq!(read.to_csv(), written.to_csv());
+        assert_eq!(read.stats.median, Duration::from_nanos(1500));
+        assert!(Record::from_csv("2023-12-01T00:00:00+00:00,abc1234,2023,5,part1").is_none());
+        assert!(Record::from_csv(&written.to_csv().replace(",1500,", ",soon,")).is_none());
+    }
+
+    #[test]
+    fn takes_the_latest_records_of_a_commit_as_baseline() {
+        let history = vec![
+            record("abc1234", 5, "part1", 100),
+            record("def5678", 5, "part1", 200),
+            record("abc1234", 5, "part1", 300),
+            record("abc1234", 5, "part2", 400),
+        ];
+        let medians = baseline(history, "abc")
+            .iter()
+            .map(|record| (record.phase.clone(), record.stats.median.as_nanos()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            medians,
+            [("part1".to_owned(), 300), ("part2".to_owned(), 400)]
+        );
+    }
+
+    #[test]
+    fn measures_slowdowns_against_the_same_phase() {
+        let baseline = vec![
+            record("abc1234", 5, "part1", 200),
+            record("abc1234", 5, "part2", 400),
+        ];
+        assert_eq!(
+            slowdown(&baseline, &record("new", 5, "part1", 250)),
+            Some(25.0)
+        );
+        assert_eq!(
+            slowdown(&baseline, &record("new", 5, "part2", 300)),
+            Some(-25.0)
+        );
+        assert_eq!(slowdown(&baseline, &record("new", 6, "part1", 250)), None);
+    }
+}
@@ -0,0 +1,48 @@
+use std::{fs, path::Path};
+
+use crate::{
+    client::{AocClient, ClientError},
+    days::Solution,
+    release,
+};
+
+/// What `download` did with the input of a day.
+pub enum Download {
+    Saved(String),
+    /// The input was already on disk and has been left alone.
+    Cached(String),
+}
+
+/// Downloads the input of `solution` to its input path, unless it is already there.
+///
+/// Puzzles that haven't unlocked yet are refused. With `force` an existing
+/// input is downloaded again. The input is written to a temporary file first and
+/// then moved into place, so a failed download never leaves a truncated input
+/// behind.
+pub fn download(
+    client: &AocClient,
+    solution: &Solution,
+    force: bool,
+) -> Result<Download, ClientError> {
+    let path = solution.input_path();
+    // An empty input is a placeholder left by `new`
+    if !force && fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Download::Cached(path));
+    }
+
+    if let Some(wait) = release::until_unlock(solution.year, solution.day) {
+        return Err(ClientError::Locked(wait));
+    }
+    let input = client.input(solution.year, solution.day)?;
+    write_atomically(&path, &input).map_err(ClientError::File)?;
+    Ok(Download::Saved(path))
+}
+
+fn write_atomically(path: &str, content: &str) -> std::io::Result<()> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temporary = format!("{}.tmp", path);
+    fs::write(&temporary, content)?;
+    fs::rename(&temporary, path)
+}
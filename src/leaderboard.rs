@@ -0,0 +1,272 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{TimeZone, Utc};
+use serde::Deserialize;
+
+use crate::{dirs, release};
+
+/// Advent of Code asks not to fetch a leaderboard more often than this.
+const CACHE_TIME: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard as served by `/<year>/leaderboard/private/view/<id>.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    event: String,
+    members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: usize,
+    local_score: usize,
+    last_star_ts: i64,
+    /// The stars earned on each day, keyed by day and then by part.
+    completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+impl Member {
+    fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn star(&self, day: usize, part: usize) -> Option<&Star> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+    }
+
+    /// How long after the puzzle unlocked the member got the star.
+    fn star_time(&self, year: usize, day: usize, part: usize) -> Option<Duration> {
+        let star = self.star(day, part)?;
+        (Utc.timestamp_opt(star.get_star_ts, 0).single()? - release::unlock_time(year, day))
+            .to_std()
+            .ok()
+    }
+}
+
+impl Leaderboard {
+    pub fn from_json(json: &str) -> Result<Leaderboard, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn year(&self) -> usize {
+        self.event.parse().unwrap_or_default()
+    }
+
+    /// Members by local score, ties going to whoever got their last star first.
+    fn ranking(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|member| {
+            (
+                std::cmp::Reverse(member.local_score),
+                member.last_star_ts,
+                member.id,
+            )
+        });
+        members
+    }
+
+    /// A table of every member's local score and stars per day.
+    ///
+    /// `*` marks a day with both stars and `.` a day with only the first one.
+    pub fn render(&self) -> String {
+        let members = self.ranking();
+        let days = members
+            .iter()
+            .flat_map(|member| member.completion_day_level.keys())
+            .filter_map(|day| day.parse().ok())
+            .max()
+            .unwrap_or(1);
+        let width = members
+            .iter()
+            .map(|member| member.name().chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut table = String::new();
+        let digits = |digit: fn(usize) -> usize| {
+            (1..=days)
+                .map(|day| match digit(day) {
+                    0 if day < 10 => ' ',
+                    digit => char::from_digit(digit as u32, 10).unwrap(),
+                })
+                .collect::<String>()
+        };
+        if days >= 10 {
+            let indent = " ".repeat(4 + 1 + 5 + 1 + width + 1);
+            writeln!(table, "{}{}", indent, digits(|day| day / 10)).unwrap();
+        }
+        writeln!(
+            table,
+            "{:>4} {:>5} {:<width$} {} {:>5}  Last star",
+            "Rank",
+            "Score",
+            "Name",
+            digits(|day| day % 10),
+            "Stars"
+        )
+        .unwrap();
+
+        for (rank, member) in (1..).zip(&members) {
+            let stars = (1..=days)
+                .map(|day| match (member.star(day, 1), member.star(day, 2)) {
+                    (Some(_), Some(_)) => '*',
+                    (Some(_), None) => '.',
+                    _ => ' ',
+                })
+                .collect::<String>();
+            let last_star = Utc
+                .timestamp_opt(member.last_star_ts, 0)
+                .single()
+                .filter(|_| member.stars > 0)
+                .map(|time| {
+                    time.with_timezone(&release::timezone())
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            writeln!(
+                table,
+                "{:>4} {:>5} {:<width$} {} {:>5}  {}",
+                rank,
+                member.local_score,
+                member.name(),
+                stars,
+                member.stars,
+                last_star
+            )
+            .unwrap();
+        }
+
+        trim_lines(&table)
+    }
+
+    /// A table of how long after the unlock each member got the stars of `day`.
+    pub fn render_day(&self, day: usize) -> String {
+        let year = self.year();
+        let mut members = self
+            .ranking()
+            .into_iter()
+            .filter(|member| member.star(day, 1).is_some())
+            .collect::<Vec<_>>();
+        members.sort_by_key(|member| {
+            (
+                member.star_time(year, day, 2).unwrap_or(Duration::MAX),
+                member.star_time(year, day, 1),
+            )
+        });
+        let width = members
+            .iter()
+            .map(|member| member.name().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let mut table = String::new();
+        writeln!(
+            table,
+            "{:>4} {:<width$} {:>12} {:>12}",
+            "Rank", "Name", "Part 1", "Part 2"
+        )
+        .unwrap();
+        for (rank, member) in (1..).zip(&members) {
+            let time = |part| {
+                member
+                    .star_time(year, day, part)
+                    .map(release::format_countdown)
+                    .unwrap_or_default()
+            };
+            writeln!(
+                table,
+                "{:>4} {:<width$} {:>12} {:>12}",
+                rank,
+                member.name(),
+                time(1),
+                time(2)
+            )
+            .unwrap();
+        }
+
+        trim_lines(&table)
+    }
+}
+
+fn trim_lines(table: &str) -> String {
+    table
+        .lines()
+        .map(|line| line.trim_end().to_owned() + "\n")
+        .collect()
+}
+
+fn cache_path(year: usize, id: &str) -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(format!("leaderboard-{}-{}.json", year, id)))
+}
+
+/// The leaderboard JSON fetched less than 15 minutes ago, if there is one.
+pub fn cached(year: usize, id: &str) -> Option<String> {
+    let path = cache_path(year, id)?;
+    let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+    let age = SystemTime::now().duration_since(modified.ok()?).ok()?;
+    if age < CACHE_TIME {
+        fs::read_to_string(path).ok()
+    } else {
+        None
+    }
+}
+
+/// Keeps the leaderboard JSON around for `cached`.
+pub fn store(year: usize, id: &str, json: &str) {
+    if let Some(path) = cache_path(year, id) {
+        // Caching is best effort, the next run just fetches again
+        let _ = fs::create_dir_all(path.parent().unwrap()).and_then(|_| fs::write(path, json));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Leaderboard;
+
+    const JSON: &str = include_str!("../fixtures/leaderboard.json");
+
+    #[test]
+    fn renders_members_by_score() {
+        assert_eq!(
+            Leaderboard::from_json(JSON).unwrap().render(),
+            "\
+Rank Score Name                   123 Stars  Last star
+   1    14 Jane Doe               **.     5  2023-12-03 00:00:00
+   2    10 John Roe               **      4  2023-12-02 01:00:00
+   3     1 (anonymous user #3003) .       1  2023-12-01 12:00:00
+   4     0 Idle Ida                       0
+"
+        );
+    }
+
+    #[test]
+    fn renders_star_times_of_a_day() {
+        assert_eq!(
+            Leaderboard::from_json(JSON).unwrap().render_day(1),
+            "\
+Rank Name                         Part 1       Part 2
+   1 Jane Doe                     5m 00s      15m 00s
+   2 John Roe                     2m 00s      30m 00s
+   3 (anonymous user #3003)  12h 00m 00s
+"
+        );
+    }
+}
@@ -1,15 +1,33 @@
-use std::{fs, time::Instant};
+use std::{fs, io::Write, process::ExitCode, sync::Mutex, thread, time::Duration};
 
-use chrono::{Datelike, Local};
+use answers::Answers;
+use bench::{BenchConfig, Comparison};
+use chrono::Local;
 use clap::{Parser, Subcommand};
+use client::{AocClient, ClientConfig, ClientError, SubmitOutcome};
+use days::Solution;
+use inputs::Download;
+use leaderboard::Leaderboard;
+use puzzle::Puzzle;
+use runner::{Format, RunOptions};
 
-use days::*;
-
+mod answers;
+mod bench;
+mod client;
 mod days;
+mod dirs;
 mod helpers;
+mod history;
+mod inputs;
+mod leaderboard;
 mod parser;
-
-const YEAR: usize = 2023;
+mod puzzle;
+mod release;
+mod runner;
+mod scaffold;
+mod session;
+mod watch;
+mod years;
 
 #[derive(Parser)]
 #[command(author, version)]
@@ -17,6 +35,29 @@ const YEAR: usize = 2023;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_BASE_URL",
+        default_value = client::DEFAULT_BASE_URL,
+        help = "The Advent of Code server to talk to"
+    )]
+    base_url: String,
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_USER_AGENT",
+        default_value = client::DEFAULT_USER_AGENT,
+        help = "How to identify to the server, preferably with your contact details"
+    )]
+    user_agent: String,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 1.0,
+        help = "The minimum number of seconds between two requests to the server"
+    )]
+    min_delay: f64,
 }
 
 #[derive(Subcommand)]
@@ -26,6 +67,47 @@ enum Commands {
         day: Option<String>,
         #[arg(short, long, help = "Runs all days sequentially")]
         all: bool,
+        #[arg(
+            short,
+            long,
+            requires = "all",
+            help = "Runs the days of --all concurrently"
+        )]
+        parallel: bool,
+        #[arg(
+            short,
+            long,
+            help = "The year of the day you want to run, defaults to the latest year"
+        )]
+        year: Option<usize>,
+        #[arg(
+            short,
+            long,
+            help = "Compares the answers against answers/<year>.toml and fails on regressions"
+        )]
+        check: bool,
+        #[arg(
+            long,
+            requires = "check",
+            help = "Adds the answers of parts without a verified answer to answers/<year>.toml"
+        )]
+        record: bool,
+        #[arg(
+            short,
+            long,
+            help = "Runs the examples from the puzzle description instead of the input"
+        )]
+        example: bool,
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t = Format::Text,
+            help = "How to print the results"
+        )]
+        format: Format,
+        #[arg(long, help = "Never downloads missing inputs")]
+        offline: bool,
     },
     GetInput {
         #[arg(
@@ -35,65 +117,406 @@ enum Commands {
         day: Option<String>,
         #[arg(short, long, help = "Downloads input for all days sequentially")]
         all: bool,
+        #[arg(
+            short,
+            long,
+            help = "The year of the day you want to get the input for, defaults to the latest year"
+        )]
+        year: Option<usize>,
+        #[arg(
+            short,
+            long,
+            help = "Downloads the input again even if it already exists"
+        )]
+        force: bool,
+        #[arg(
+            short,
+            long,
+            conflicts_with = "all",
+            help = "Waits until the puzzle unlocks and then downloads the input"
+        )]
+        wait: bool,
+    },
+    GetPuzzle {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day you want to get the puzzle description for"
+        )]
+        day: String,
+        #[arg(
+            short,
+            long,
+            help = "The year of the day you want to get the puzzle for, defaults to the latest year"
+        )]
+        year: Option<usize>,
+    },
+    Bench {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day you want to benchmark"
+        )]
+        day: Option<String>,
+        #[arg(short, long, help = "Benchmarks all days and prints them in one table")]
+        all: bool,
+        #[arg(
+            short,
+            long,
+            help = "The year of the day you want to benchmark, defaults to the latest year"
+        )]
+        year: Option<usize>,
+        #[arg(
+            short,
+            long,
+            default_value_t = 100,
+            help = "How many measured runs to do of each phase"
+        )]
+        iterations: usize,
+        #[arg(
+            short,
+            long,
+            default_value_t = 3,
+            help = "How many unmeasured runs to do of each phase first"
+        )]
+        warmup: usize,
+        #[arg(
+            long,
+            default_value_t = 5.0,
+            help = "Stop measuring a phase after this many seconds"
+        )]
+        max_time: f64,
+        #[arg(
+            short,
+            long,
+            value_name = "COMMIT",
+            help = "Compares against the results saved for a commit in bench/history.csv"
+        )]
+        compare: Option<String>,
+        #[arg(
+            long,
+            default_value_t = 10.0,
+            help = "How many percent slower a phase may get before it is flagged"
+        )]
+        threshold: f64,
+        #[arg(long, help = "Doesn't save the results to bench/history.csv")]
+        no_save: bool,
+    },
+    Submit {
+        #[arg(value_name = "DAY", help = "The number of the day you want to submit")]
+        day: String,
+        #[arg(
+            value_name = "PART",
+            value_parser = clap::value_parser!(u8).range(1..=2),
+            help = "The part you want to submit"
+        )]
+        part: u8,
+        #[arg(
+            short,
+            long,
+            help = "The year of the day you want to submit, defaults to the latest year"
+        )]
+        year: Option<usize>,
+    },
+    Watch {
+        #[arg(value_name = "DAY", help = "The number of the day you want to watch")]
+        day: String,
+        #[arg(
+            short,
+            long,
+            help = "The year of the day you want to watch, defaults to the latest year"
+        )]
+        year: Option<usize>,
+    },
+    New {
+        #[arg(
+            value_name = "DAY",
+            help = "The number of the day you want to start on"
+        )]
+        day: usize,
+        #[arg(
+            short,
+            long,
+            help = "The year of the day you want to start on, defaults to the latest year"
+        )]
+        year: Option<usize>,
+    },
+    Leaderboard {
+        #[arg(
+            value_name = "ID",
+            help = "The id of the private leaderboard, as in its URL"
+        )]
+        id: String,
+        #[arg(
+            short,
+            long,
+            help = "The year of the leaderboard, defaults to the latest year"
+        )]
+        year: Option<usize>,
+        #[arg(
+            short,
+            long,
+            help = "Shows how long after the unlock everyone got the stars of this day"
+        )]
+        day: Option<usize>,
+    },
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommands,
     },
 }
 
-fn main() {
+#[derive(Subcommand)]
+enum AuthCommands {
+    /// Checks that the session cookie is valid and shows who it belongs to
+    Check,
+}
+
+fn main() -> ExitCode {
     let cli = Cli::parse();
+    let client_config = ClientConfig {
+        base_url: cli.base_url.clone(),
+        user_agent: cli.user_agent.clone(),
+        min_delay: Duration::from_secs_f64(cli.min_delay),
+    };
 
     match &cli.command {
-        Commands::Run { day, all } => {
-            if *all {
-                run_all_days();
+        Commands::Run {
+            day,
+            all,
+            parallel,
+            year,
+            check,
+            record,
+            example,
+            format,
+            offline,
+        } => {
+            let year = parse_year(*year);
+            let options = RunOptions {
+                answers: check.then(|| Answers::load(year)),
+                record: record.then(|| Mutex::new(Answers::load(year))),
+                examples: *example,
+                format: *format,
+                // Missing inputs can only be downloaded with a session
+                client: (!*offline && !*example)
+                    .then(|| AocClient::new(&client_config).ok())
+                    .flatten(),
+            };
+            let regressed = if *all {
+                runner::run_all_days(year, &options, *parallel)
             } else {
                 match day {
-                    Some(day) => run_day(parse_day(day)),
+                    Some(day) => {
+                        runner::run_day(find_solution(year, parse_day(year, day)), &options)
+                    }
                     None => {
-                        println!("No day parameter specified, attempting to run today");
-                        let now_day = get_today();
-                        println!("Running day {}", now_day);
-                        run_day(now_day);
+                        eprintln!("No day parameter specified, attempting to run the latest day");
+                        let latest_day = get_latest_day(year);
+                        eprintln!("Running day {}", latest_day);
+                        runner::run_day(find_solution(year, latest_day), &options)
                     }
                 }
+            };
+            if let Some(record) = options.record {
+                record.into_inner().unwrap().save(year);
+                eprintln!("Recorded the new answers in answers/{}.toml", year);
+            }
+            if regressed {
+                eprintln!("Some answers no longer match the verified answers");
+                return ExitCode::FAILURE;
             }
         }
-        Commands::GetInput { day, all } => {
-            if *all {
-                download_all_input();
+        Commands::GetInput {
+            day,
+            all,
+            year,
+            force,
+            wait,
+        } => {
+            let year = parse_year(*year);
+            let downloaded = if *all {
+                let unlocked = years::solutions(year)
+                    .filter(|solution| release::until_unlock(year, solution.day).is_none());
+                download_inputs(&client_config, unlocked, *force)
             } else {
-                match day {
-                    Some(day) => download_input(parse_day(day)),
+                let day = match day {
+                    Some(day) => parse_day(year, day),
+                    // Waiting only makes sense for a day that hasn't unlocked yet
+                    None if *wait => {
+                        println!(
+                            "No day parameter specified, attempting to download the next input to unlock"
+                        );
+                        let next_day =
+                            release::next_locked_day(year).unwrap_or_else(|| get_latest_day(year));
+                        println!("Getting input for day {}", next_day);
+                        next_day
+                    }
                     None => {
                         println!(
-                            "No day parameter specified, attempting to download today's input"
+                            "No day parameter specified, attempting to download the latest input"
                         );
-                        let now_day = get_today();
-                        println!("Getting input for day {}", now_day);
-                        download_input(now_day);
+                        let latest_day = get_latest_day(year);
+                        println!("Getting input for day {}", latest_day);
+                        latest_day
                     }
+                };
+                if let Some(unlock) = release::until_unlock(year, day).filter(|_| *wait) {
+                    println!(
+                        "Day {} unlocks in {}, waiting",
+                        day,
+                        release::format_countdown(unlock)
+                    );
+                    thread::sleep(unlock + release::UNLOCK_GRACE);
                 }
+                download_inputs(
+                    &client_config,
+                    [find_solution(year, day)].into_iter(),
+                    *force,
+                )
+            };
+            if !downloaded {
+                return ExitCode::FAILURE;
+            }
+        }
+        Commands::GetPuzzle { day, year } => {
+            let year = parse_year(*year);
+            if !download_puzzle(&client_config, year, parse_day(year, day)) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Commands::Bench {
+            day,
+            all,
+            year,
+            iterations,
+            warmup,
+            max_time,
+            compare,
+            threshold,
+            no_save,
+        } => {
+            let year = parse_year(*year);
+            let config = BenchConfig {
+                warmup: *warmup,
+                iterations: *iterations,
+                max_time: Duration::from_secs_f64(*max_time),
+            };
+            let comparison = compare.as_ref().map(|commit| Comparison {
+                baseline: history::baseline(history::load(), commit),
+                threshold: *threshold,
+            });
+            let (records, slower) = if *all {
+                bench::bench_days(years::solutions(year), &config, comparison.as_ref())
+            } else {
+                let day = match day {
+                    Some(day) => parse_day(year, day),
+                    None => {
+                        println!(
+                            "No day parameter specified, attempting to benchmark the latest day"
+                        );
+                        get_latest_day(year)
+                    }
+                };
+                bench::bench_days(
+                    [find_solution(year, day)].into_iter(),
+                    &config,
+                    comparison.as_ref(),
+                )
+            };
+            if !*no_save {
+                history::append(&records);
+            }
+            if slower {
+                println!(
+                    "Some phases got more than {}% slower than {}",
+                    threshold,
+                    compare.as_deref().unwrap_or_default()
+                );
+                return ExitCode::FAILURE;
+            }
+        }
+        Commands::Submit { day, part, year } => {
+            let year = parse_year(*year);
+            if !submit(&client_config, year, parse_day(year, day), (*part).into()) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Commands::Watch { day, year } => {
+            let year = parse_year(*year);
+            watch::watch(find_solution(year, parse_day(year, day)));
+        }
+        Commands::New { day, year } => {
+            let year = year.unwrap_or_else(years::latest_year);
+            if !(1..=25).contains(day) {
+                panic!("{} is not a valid day. Only days 1-25 are allowed.", day)
+            }
+            match scaffold::new_day(year, *day) {
+                Ok(paths) => {
+                    for path in paths {
+                        println!("Wrote {}", path);
+                    }
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Commands::Leaderboard { id, year, day } => {
+            let year = year.unwrap_or_else(years::latest_year);
+            let Some(leaderboard) = get_leaderboard(&client_config, year, id) else {
+                return ExitCode::FAILURE;
+            };
+            match day {
+                Some(day) => print!("{}", leaderboard.render_day(*day)),
+                None => print!("{}", leaderboard.render()),
+            }
+        }
+        Commands::Auth {
+            command: AuthCommands::Check,
+        } => {
+            if !check_session(&client_config) {
+                return ExitCode::FAILURE;
             }
         }
     }
+
+    ExitCode::SUCCESS
+}
+
+// Panics if no puzzle of the year has unlocked yet
+fn get_latest_day(year: usize) -> usize {
+    release::latest_unlocked_day(year).unwrap_or_else(|| {
+        panic!(
+            "No puzzle of {} has unlocked yet, the first one unlocks in {}. Please specify a day",
+            year,
+            release::format_countdown(release::until_unlock(year, 1).unwrap_or_default())
+        )
+    })
 }
 
-fn get_today() -> usize {
-    let now = Local::now();
-    let now_day = now.day();
-    if now.month() == 12 && (1..=25).contains(&now_day) {
-        now_day.try_into().unwrap()
-    } else {
-        panic!("Today is not a valid Advent of Code day. Please specify a day");
+fn parse_year(year: Option<usize>) -> usize {
+    match year {
+        Some(year) => {
+            if years::solutions(year).next().is_some() {
+                year
+            } else {
+                panic!("{} is not a year with registered solutions.", year)
+            }
+        }
+        None => years::latest_year(),
     }
 }
 
-fn parse_day(day: &str) -> usize {
+fn parse_day(year: usize, day: &str) -> usize {
     match day.parse() {
+        Ok(i) if !(1..=25).contains(&i) => {
+            panic!("{} is not a valid day. Only days 1-25 are allowed.", i)
+        }
         Ok(i) => {
-            if (i..=25).contains(&i) {
+            if years::find(year, i).is_some() {
                 i
             } else {
-                panic!("{} is not a valid day. Only days 1-25 are allowed.", i)
+                panic!("Day {} has no solution registered for {}.", i, year)
             }
         }
         Err(_) => {
@@ -102,71 +525,196 @@ fn parse_day(day: &str) -> usize {
     }
 }
 
-fn run_all_days() {
-    (1..=25).map(run_day).collect()
+// Panics if there is no registered solution for the day
+fn find_solution(year: usize, day: usize) -> &'static Solution {
+    years::find(year, day).unwrap_or_else(|| panic!("Provided unsupported day {}", day))
 }
-// Panics if you provide a value outside the range of 1 to 25
-fn run_day(day: usize) {
-    println!("======== DAY {} ========", day);
-    // I'd like to do this with a macro, but I'm not sure how to do it.
-    let input_fp = &format!("inputs/day{:02}.txt", day);
-    match day {
-        1 => day01::Day01::run_day(input_fp),
-        2 => day02::Day02::run_day(input_fp),
-        3 => day03::Day03::run_day(input_fp),
-        4 => day04::Day04::run_day(input_fp),
-        5 => day05::Day05::run_day(input_fp),
-        6 => day06::Day06::run_day(input_fp),
-        7 => day07::Day07::run_day(input_fp),
-        8 => day08::Day08::run_day(input_fp),
-        9 => day09::Day09::run_day(input_fp),
-        10 => day10::Day10::run_day(input_fp),
-        11 => day11::Day11::run_day(input_fp),
-        12 => day12::Day12::run_day(input_fp),
-        13 => day13::Day13::run_day(input_fp),
-        14 => day14::Day14::run_day(input_fp),
-        15 => day15::Day15::run_day(input_fp),
-        16 => day16::Day16::run_day(input_fp),
-        17 => day17::Day17::run_day(input_fp),
-        18 => day18::Day18::run_day(input_fp),
-        19 => day19::Day19::run_day(input_fp),
-        20 => day20::Day20::run_day(input_fp),
-        21 => day21::Day21::run_day(input_fp),
-        22 => day22::Day22::run_day(input_fp),
-        23 => day23::Day23::run_day(input_fp),
-        24 => day24::Day24::run_day(input_fp),
-        25 => day25::Day25::run_day(input_fp),
-        d => panic!("Provided unsupported day {}", d),
+
+/// Downloads the inputs that aren't there yet, returning whether all of them are now available.
+fn download_inputs<'a>(
+    config: &ClientConfig,
+    solutions: impl Iterator<Item = &'a Solution>,
+    force: bool,
+) -> bool {
+    let client = match AocClient::new(config) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("{}", e);
+            return false;
+        }
+    };
+
+    let mut downloaded = true;
+    for solution in solutions {
+        match inputs::download(&client, solution, force) {
+            Ok(Download::Saved(path)) => println!("Successfully downloaded input to {}", path),
+            Ok(Download::Cached(path)) => {
+                println!("{} already exists, use --force to download it again", path)
+            }
+            Err(e) => {
+                println!("Could not get input for day {}: {}", solution.day, e);
+                downloaded = false;
+                // The remaining days would fail the same way
+                if matches!(e, ClientError::NotLoggedIn | ClientError::TooManyRequests) {
+                    break;
+                }
+            }
+        }
     }
+    downloaded
 }
 
-fn download_all_input() {
-    (1..=25).map(download_input).collect()
+/// Saves the description and example candidates of a puzzle, returning whether that worked.
+fn download_puzzle(config: &ClientConfig, year: usize, day: usize) -> bool {
+    if let Some(wait) = release::until_unlock(year, day) {
+        println!("{}", ClientError::Locked(wait));
+        return false;
+    }
+    let html = match AocClient::new(config).and_then(|client| client.puzzle(year, day)) {
+        Ok(html) => html,
+        Err(e) => {
+            println!("Could not get puzzle for day {}: {}", day, e);
+            return false;
+        }
+    };
+
+    let puzzle = Puzzle::from_html(&html);
+    if puzzle.markdown.is_empty() {
+        println!(
+            "Could not find the puzzle description for day {} on the page",
+            day
+        );
+        return false;
+    }
+    let paths = puzzle.save(year, day).unwrap();
+    println!("Saved the puzzle description to {}", paths[0]);
+    for path in &paths[1..] {
+        println!("Saved a possible example to {}", path);
+    }
+    true
 }
 
-fn download_input(day: usize) {
-    // Read session cookie from .session file
-    let session = fs::read_to_string(".session").expect("Could not find .session file");
-    let session = session.trim();
-    let url = format!("https://adventofcode.com/{}/day/{}/input", YEAR, day);
-    let client = reqwest::blocking::Client::new();
-    let response = client
-        .get(url)
-        .header("cookie", format!("session={};", session))
-        .send()
-        .unwrap();
+/// Loads a private leaderboard, from the cache if it was fetched less than 15 minutes ago.
+fn get_leaderboard(config: &ClientConfig, year: usize, id: &str) -> Option<Leaderboard> {
+    let (json, fetched) = match leaderboard::cached(year, id) {
+        Some(json) => (json, false),
+        None => match AocClient::new(config).and_then(|client| client.leaderboard(year, id)) {
+            Ok(json) => (json, true),
+            Err(e) => {
+                println!("Could not get leaderboard {}: {}", id, e);
+                return None;
+            }
+        },
+    };
 
-    if response.status().is_success() {
-        let mut text = response.text().unwrap();
-        // Remove trailing newline
-        text.pop();
-        let path = format!("inputs/day{:02}.txt", day);
-        fs::write(&path, text).unwrap();
-        println!("Successfully downloaded input to {}", &path);
-    } else {
-        panic!(
-            "Could not get input for day {}. Is your correct session cookie in your .session file?",
-            day
-        )
+    match Leaderboard::from_json(&json) {
+        Ok(leaderboard) => {
+            // Only a real leaderboard is cached, not the login page of an expired session
+            if fetched {
+                leaderboard::store(year, id, &json);
+            }
+            Some(leaderboard)
+        }
+        Err(e) => {
+            println!(
+                "Could not read leaderboard {}, do you have access to it? {}",
+                id, e
+            );
+            None
+        }
+    }
+}
+
+/// Solves a part and submits the answer, returning whether it was correct.
+fn submit(config: &ClientConfig, year: usize, day: usize, part: usize) -> bool {
+    let solution = find_solution(year, day);
+    let answer = match (solution.answer)(&solution.input_path(), part) {
+        Ok(answer) => answer,
+        Err(e) => {
+            println!("{}", e);
+            return false;
+        }
+    };
+    println!("Submitting {} for day {} part {}", answer, day, part);
+
+    let client = match AocClient::new(config) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("{}", e);
+            return false;
+        }
+    };
+    let outcome = match client.submit(year, day, part, &answer) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            println!("Could not submit the answer: {}", e);
+            return false;
+        }
+    };
+    match &outcome {
+        SubmitOutcome::Correct => {
+            println!("That's the right answer!");
+            let mut answers = Answers::load(year);
+            answers.set(day, part, &answer);
+            answers.save(year);
+        }
+        SubmitOutcome::TooHigh => println!("That's not the right answer, it is too high"),
+        SubmitOutcome::TooLow => println!("That's not the right answer, it is too low"),
+        SubmitOutcome::Wrong => println!("That's not the right answer"),
+        SubmitOutcome::RateLimited(Some(wait)) => println!(
+            "You gave an answer too recently, wait {}s before trying again",
+            wait.as_secs()
+        ),
+        SubmitOutcome::RateLimited(None) => {
+            println!("You gave an answer too recently, wait before trying again")
+        }
+        SubmitOutcome::AlreadySolved => println!("This part is already solved"),
+        SubmitOutcome::Unknown(message) => println!("Unexpected response: {}", message),
+    }
+    record_submission(year, day, part, &answer, &outcome);
+    outcome == SubmitOutcome::Correct
+}
+
+/// Reports who the session cookie belongs to, returning whether it is valid.
+fn check_session(config: &ClientConfig) -> bool {
+    let client = match AocClient::new(config) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("{}", e);
+            return false;
+        }
+    };
+    println!("Using the session cookie from {}", client.session().source);
+
+    match client.user() {
+        Ok(user) => {
+            println!("Logged in as {}", user);
+            true
+        }
+        Err(e) => {
+            println!("{}", e);
+            false
+        }
     }
 }
+
+/// Appends the outcome of a submission to `answers/submissions.log`.
+fn record_submission(year: usize, day: usize, part: usize, answer: &str, outcome: &SubmitOutcome) {
+    fs::create_dir_all("answers").unwrap();
+    let mut log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open("answers/submissions.log")
+        .unwrap();
+    writeln!(
+        log,
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        Local::now().to_rfc3339(),
+        year,
+        day,
+        part,
+        answer,
+        outcome
+    )
+    .unwrap();
+}
@@ -1,19 +1,79 @@
-use nom::{error::Error, Err};
+use std::fmt::Display;
+
+pub use aoc_derive::Parse;
+use nom::{
+    character::complete::alphanumeric1,
+    combinator::map,
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    Err, IResult,
+};
+
+use crate::{days::SolutionError, helpers::parse_ndigit};
+
+/// The result of the parsers of the days, which keep track of the context they fail in.
+pub type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// Types with a parser of their own, which `#[derive(Parse)]` uses for the fields of a struct.
+///
+/// Derive it with a format like `#[parse("Card {id}: {winning: list< >} | {selected: list< >}")]`
+/// to parse a line of the input into a struct.
+pub trait Parse: Sized {
+    fn parse(input: &str) -> ParseResult<'_, Self>;
+}
+
+macro_rules! parse_number {
+    ($($number:ty),*) => {
+        $(
+            impl Parse for $number {
+                fn parse(input: &str) -> ParseResult<'_, Self> {
+                    parse_ndigit("number")(input)
+                }
+            }
+        )*
+    };
+}
+
+parse_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A word of letters and digits.
+impl Parse for String {
+    fn parse(input: &str) -> ParseResult<'_, Self> {
+        map(alphanumeric1, ToOwned::to_owned)(input)
+    }
+}
 
 #[derive(Debug)]
 pub enum MyErr {
     FileError(std::io::Error),
-    ParseError(Err<Error<String>>),
+    ParseError(Diagnostic),
+    Solution(SolutionError),
 }
 
-impl From<Err<Error<&str>>> for MyErr {
-    fn from(e: Err<Error<&str>>) -> MyErr {
-        let inner_err = match e {
-            Err::Incomplete(n) => Err::Incomplete(n),
-            Err::Error(e) => Err::Error(conv_error(e)),
-            Err::Failure(e) => Err::Failure(conv_error(e)),
-        };
-        MyErr::ParseError(inner_err)
+impl MyErr {
+    /// Converts a parse error into a diagnostic pointing into `input`.
+    pub fn parse(input: &str, e: Err<VerboseError<&str>>) -> MyErr {
+        MyErr::ParseError(Diagnostic::new(input, e))
+    }
+
+    /// Reports that parsing stopped before the end of `input`, at the start of `remaining`.
+    pub fn trailing(input: &str, remaining: &str) -> MyErr {
+        let remaining = remaining.trim_start();
+        MyErr::ParseError(Diagnostic::at(
+            input,
+            remaining,
+            "parsing stopped before the end of the input".to_owned(),
+            vec![],
+        ))
+    }
+}
+
+impl Display for MyErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MyErr::FileError(e) => write!(f, "Could not read input: {}", e),
+            MyErr::ParseError(e) => write!(f, "Could not parse input {}", e),
+            MyErr::Solution(e) => write!(f, "Could not solve: {}", e),
+        }
     }
 }
 
@@ -23,9 +83,169 @@ impl From<std::io::Error> for MyErr {
     }
 }
 
-fn conv_error(e: Error<&str>) -> Error<String> {
-    Error {
-        input: e.input.to_owned(),
-        code: e.code,
+impl From<SolutionError> for MyErr {
+    fn from(e: SolutionError) -> MyErr {
+        MyErr::Solution(e)
+    }
+}
+
+/// Where and why parsing failed, without holding on to the rest of the input.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    /// The line of the input the parser failed on.
+    pub source_line: String,
+    /// What the innermost parser expected.
+    pub kind: String,
+    /// The contexts the parser was in, innermost first.
+    pub context: Vec<&'static str>,
+}
+
+impl Diagnostic {
+    fn new(input: &str, e: Err<VerboseError<&str>>) -> Diagnostic {
+        let errors = match e {
+            Err::Error(e) | Err::Failure(e) => e.errors,
+            Err::Incomplete(_) => vec![],
+        };
+        // Parsers on complete input never ask for more, but point at the end if one does
+        let remaining = errors.first().map_or("", |(remaining, _)| *remaining);
+        let kind = errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Char(c) => Some(format!("expected {:?}", c)),
+                VerboseErrorKind::Nom(ErrorKind::MapOpt | ErrorKind::MapRes) => {
+                    Some("invalid value".to_owned())
+                }
+                VerboseErrorKind::Nom(kind) => Some(kind.description().to_owned()),
+                VerboseErrorKind::Context(_) => None,
+            })
+            .unwrap_or_else(|| "incomplete input".to_owned());
+        let context = errors
+            .iter()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(*context),
+                _ => None,
+            })
+            .collect();
+        Diagnostic::at(input, remaining, kind, context)
+    }
+
+    /// Points at the start of `remaining`, which has to be the end of `input`.
+    fn at(input: &str, remaining: &str, kind: String, context: Vec<&'static str>) -> Diagnostic {
+        let offset = input.len() - remaining.len();
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Diagnostic {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            kind,
+            context,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "at line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )?;
+        for context in &self.context {
+            writeln!(f, "  while parsing {}", context)?;
+        }
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag, character::complete::digit1, error::context, sequence::separated_pair,
+    };
+
+    use super::{MyErr, Parse, ParseResult};
+
+    #[derive(Debug, PartialEq, Parse)]
+    #[parse("{name} moves {{{steps: list<, >}}} from {start}")]
+    struct Moves {
+        name: String,
+        steps: Vec<i32>,
+        start: u8,
+    }
+
+    fn pair(input: &str) -> ParseResult<'_, (&str, &str)> {
+        context("pair", separated_pair(digit1, tag(","), digit1))(input)
+    }
+
+    #[test]
+    fn points_at_the_failing_line_and_column() {
+        let input = "first line\n12;34\nlast line";
+        let e = pair(&input[11..]).unwrap_err();
+        assert_eq!(
+            MyErr::parse(input, e).to_string(),
+            "\
+Could not parse input at line 2, column 3: Tag
+  while parsing pair
+  |
+2 | 12;34
+  |   ^"
+        );
+    }
+
+    #[test]
+    fn points_at_where_parsing_stopped() {
+        let input = "1,2\n3,4\n5;6\n";
+        let (remaining, _) = pair(&input[4..]).unwrap();
+        assert_eq!(
+            MyErr::trailing(input, remaining).to_string(),
+            "\
+Could not parse input at line 3, column 1: parsing stopped before the end of the input
+  |
+3 | 5;6
+  | ^"
+        );
+    }
+
+    #[test]
+    fn derives_parsers_from_formats() {
+        assert_eq!(
+            Moves::parse("robot1  moves {3, -1,  2} from 7\n"),
+            Ok((
+                "\n",
+                Moves {
+                    name: "robot1".to_owned(),
+                    steps: vec![3, -1, 2],
+                    start: 7
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn derived_parsers_report_the_field() {
+        let input = "robot moves {3, 1} from 700";
+        let e = Moves::parse(input).unwrap_err();
+        assert_eq!(
+            MyErr::parse(input, e).to_string(),
+            "\
+Could not parse input at line 1, column 25: invalid value
+  while parsing number
+  while parsing start
+  while parsing Moves
+  |
+1 | robot moves {3, 1} from 700
+  |                         ^"
+        );
     }
 }
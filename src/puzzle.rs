@@ -0,0 +1,274 @@
+use std::{fs, io};
+
+/// A puzzle description converted from the HTML of its page.
+pub struct Puzzle {
+    /// Both parts of the description as Markdown, or only part 1 until it is solved.
+    pub markdown: String,
+    /// The contents of every `<pre><code>` block, which are usually the example inputs.
+    pub examples: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn from_html(html: &str) -> Puzzle {
+        let articles = articles(html);
+        Puzzle {
+            markdown: articles
+                .iter()
+                .map(|article| to_markdown(article))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            examples: articles
+                .iter()
+                .flat_map(|article| code_blocks(article))
+                .collect(),
+        }
+    }
+
+    /// Saves the description to `puzzles/<year>/dayNN.md` and every example to
+    /// `puzzles/<year>/dayNN-K.txt`, returning the paths written to.
+    pub fn save(&self, year: usize, day: usize) -> io::Result<Vec<String>> {
+        let dir = format!("puzzles/{}", year);
+        fs::create_dir_all(&dir)?;
+
+        let description = format!("{}/day{:02}.md", dir, day);
+        fs::write(&description, &self.markdown)?;
+        let mut paths = vec![description];
+        for (i, example) in self.examples.iter().enumerate() {
+            let path = format!("{}/day{:02}-{}.txt", dir, day, i + 1);
+            fs::write(&path, example)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// The inner HTML of every `<article>` on the page.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+    while let Some((_, after)) = rest.split_once("<article") {
+        let Some((_, after)) = after.split_once('>') else {
+            break;
+        };
+        let Some((article, after)) = after.split_once("</article>") else {
+            break;
+        };
+        articles.push(article);
+        rest = after;
+    }
+    articles
+}
+
+fn code_blocks(article: &str) -> Vec<String> {
+    article
+        .split("<pre><code>")
+        .skip(1)
+        .filter_map(|block| block.split_once("</code></pre>"))
+        .map(|(code, _)| {
+            let text = decode_entities(&strip_tags(code));
+            text.strip_suffix('\n').unwrap_or(&text).to_owned()
+        })
+        .collect()
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    /// The name of the tag, whether it closes an element, and the rest of the tag.
+    Tag(&'a str, bool, &'a str),
+}
+
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+    while !rest.is_empty() {
+        match rest.find('<') {
+            Some(0) => {
+                // A `<` that is never closed isn't a tag
+                let Some(end) = rest.find('>') else {
+                    tokens.push(Token::Text(rest));
+                    break;
+                };
+                let tag = rest[1..end].trim_end_matches('/');
+                let (closing, tag) = match tag.strip_prefix('/') {
+                    Some(tag) => (true, tag),
+                    None => (false, tag),
+                };
+                let (name, attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+                tokens.push(Token::Tag(name, closing, attributes));
+                rest = &rest[end + 1..];
+            }
+            Some(start) => {
+                tokens.push(Token::Text(&rest[..start]));
+                rest = &rest[start..];
+            }
+            None => {
+                tokens.push(Token::Text(rest));
+                rest = "";
+            }
+        }
+    }
+    tokens
+}
+
+/// Converts the HTML used in puzzle descriptions to Markdown.
+fn to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut links = vec![];
+
+    for token in tokens(html) {
+        match token {
+            Token::Text(text) if in_pre => markdown.push_str(&decode_entities(text)),
+            Token::Text(text) => {
+                let text = decode_entities(&collapse_whitespace(text));
+                if markdown.is_empty() || markdown.ends_with('\n') {
+                    markdown.push_str(text.trim_start());
+                } else {
+                    markdown.push_str(&text);
+                }
+            }
+            Token::Tag("h2", false, _) => markdown.push_str("## "),
+            Token::Tag("h2", true, _) | Token::Tag("p", true, _) => end_line(&mut markdown, 2),
+            Token::Tag("pre", false, _) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            Token::Tag("pre", true, _) => {
+                in_pre = false;
+                end_line(&mut markdown, 1);
+                markdown.push_str("```\n\n");
+            }
+            Token::Tag("code", closing, _) if !in_pre => {
+                in_code = !closing;
+                markdown.push('`');
+            }
+            Token::Tag("em", _, _) if !in_pre && !in_code => markdown.push_str("**"),
+            Token::Tag("li", false, _) => markdown.push_str("- "),
+            Token::Tag("li", true, _) | Token::Tag("br", _, _) => end_line(&mut markdown, 1),
+            Token::Tag("ul", true, _) => end_line(&mut markdown, 2),
+            Token::Tag("a", false, attributes) => {
+                links.push(attribute(attributes, "href").unwrap_or_default());
+                markdown.push('[');
+            }
+            Token::Tag("a", true, _) => {
+                markdown.push_str(&format!("]({})", links.pop().unwrap_or_default()))
+            }
+            Token::Tag(..) => {}
+        }
+    }
+
+    markdown.trim_end().to_owned() + "\n"
+}
+
+/// Ends the current line without trailing spaces, followed by empty lines up to `newlines`.
+fn end_line(markdown: &mut String, newlines: usize) {
+    markdown.truncate(markdown.trim_end_matches(' ').len());
+    let existing = markdown.len() - markdown.trim_end_matches('\n').len();
+    for _ in existing..newlines {
+        markdown.push('\n');
+    }
+}
+
+/// Replaces every run of whitespace with a single space, like a browser does.
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::new();
+    for c in text.chars() {
+        if !c.is_whitespace() {
+            collapsed.push(c);
+        } else if !collapsed.ends_with(' ') {
+            collapsed.push(' ');
+        }
+    }
+    collapsed
+}
+
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let (_, rest) = attributes.split_once(&format!("{}=\"", name))?;
+    rest.split_once('"').map(|(value, _)| value)
+}
+
+/// The text of `html` without its tags, keeping the whitespace as it is.
+pub fn strip_tags(html: &str) -> String {
+    tokens(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text),
+            Token::Tag(..) => None,
+        })
+        .collect()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strip_tags, Puzzle};
+
+    const PAGE: &str = include_str!("../fixtures/puzzle.html");
+
+    #[test]
+    fn converts_articles_to_markdown() {
+        assert_eq!(
+            Puzzle::from_html(PAGE).markdown,
+            "\
+## --- Day 1: Counting Sheep ---
+
+The shepherd has lost count of the **sheep** again, and asks you to help.
+
+Each line of the list holds the number of sheep seen in one field. For example:
+
+```
+3
+14
+1 < 2
+```
+
+Add the numbers up: `3 + 14 + 1` makes `18`. Read more about [sheep](https://en.wikipedia.org/wiki/Sheep).
+
+- Empty lines count as `0`.
+- Negative sheep don't exist.
+
+**What is the total number of sheep?**
+
+## --- Part Two ---
+
+Some fields were counted twice:
+
+```
+a & b
+```
+
+How many sheep are there really?
+"
+        );
+    }
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(Puzzle::from_html(PAGE).examples, ["3\n14\n1 < 2", "a & b"]);
+    }
+
+    #[test]
+    fn keeps_unterminated_tags_as_text() {
+        assert_eq!(strip_tags("a <b>bold</b> move <"), "a bold move <");
+        assert_eq!(strip_tags("1 <em>2</em> <3"), "1 2 <3");
+        let puzzle = Puzzle::from_html("<article>Almost <</article>");
+        assert!(puzzle.markdown.starts_with("Almost <"));
+    }
+
+    #[test]
+    fn ignores_pages_without_articles() {
+        let puzzle = Puzzle::from_html("<html><body>Please log in</body></html>");
+        assert_eq!(puzzle.markdown, "");
+        assert!(puzzle.examples.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+/// Puzzles unlock at midnight US Eastern Standard Time.
+const RELEASE_OFFSET_HOURS: i32 = 5;
+/// How long to wait past an unlock before downloading, as the server refuses requests that
+/// arrive early when our clock runs a little ahead of its clock.
+pub const UNLOCK_GRACE: Duration = Duration::from_secs(5);
+
+/// The time zone Advent of Code keeps its time in.
+pub fn timezone() -> FixedOffset {
+    FixedOffset::west_opt(RELEASE_OFFSET_HOURS * 3600).unwrap()
+}
+
+/// The moment the puzzle of `day` in `year` unlocks.
+pub fn unlock_time(year: usize, day: usize) -> DateTime<Utc> {
+    timezone()
+        .with_ymd_and_hms(year as i32, 12, day as u32, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+/// How long until the puzzle of `day` in `year` unlocks, or `None` if it already has.
+pub fn until_unlock(year: usize, day: usize) -> Option<Duration> {
+    until_unlock_at(year, day, Utc::now())
+}
+
+fn until_unlock_at(year: usize, day: usize, now: DateTime<Utc>) -> Option<Duration> {
+    (unlock_time(year, day) - now)
+        .to_std()
+        .ok()
+        .filter(|wait| !wait.is_zero())
+}
+
+/// The latest day of `year` whose puzzle has unlocked.
+pub fn latest_unlocked_day(year: usize) -> Option<usize> {
+    latest_unlocked_day_at(year, Utc::now())
+}
+
+fn latest_unlocked_day_at(year: usize, now: DateTime<Utc>) -> Option<usize> {
+    (1..=25)
+        .rev()
+        .find(|&day| until_unlock_at(year, day, now).is_none())
+}
+
+/// The first day of `year` whose puzzle is still locked.
+pub fn next_locked_day(year: usize) -> Option<usize> {
+    next_locked_day_at(year, Utc::now())
+}
+
+fn next_locked_day_at(year: usize, now: DateTime<Utc>) -> Option<usize> {
+    (1..=25).find(|&day| until_unlock_at(year, day, now).is_some())
+}
+
+/// Formats the time left until an unlock, like `2d 3h 04m 05s`.
+pub fn format_countdown(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{}d {}h {:02}m {:02}s", days, hours, minutes, seconds)
+    } else if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else {
+        format!("{}m {:02}s", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{
+        format_countdown, latest_unlocked_day_at, next_locked_day_at, unlock_time, until_unlock_at,
+    };
+
+    fn utc(month: u32, day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2023, month, day, hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn unlocks_at_midnight_eastern_time() {
+        assert_eq!(unlock_time(2023, 1), utc(12, 1, 5, 0, 0));
+        assert_eq!(unlock_time(2023, 25), utc(12, 25, 5, 0, 0));
+        assert_eq!(
+            until_unlock_at(2023, 3, utc(12, 3, 4, 58, 30)),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(until_unlock_at(2023, 3, utc(12, 3, 5, 0, 0)), None);
+    }
+
+    #[test]
+    fn knows_the_latest_unlocked_and_the_next_locked_day() {
+        let days = |now| {
+            (
+                latest_unlocked_day_at(2023, now),
+                next_locked_day_at(2023, now),
+            )
+        };
+        assert_eq!(days(utc(11, 30, 12, 0, 0)), (None, Some(1)));
+        assert_eq!(days(utc(12, 1, 5, 0, 0)), (Some(1), Some(2)));
+        assert_eq!(days(utc(12, 10, 4, 59, 59)), (Some(9), Some(10)));
+        assert_eq!(days(utc(12, 26, 0, 0, 0)), (Some(25), None));
+    }
+
+    #[test]
+    fn formats_countdowns() {
+        assert_eq!(format_countdown(Duration::from_secs(5)), "0m 05s");
+        assert_eq!(
+            format_countdown(Duration::from_secs(3 * 3600 + 65)),
+            "3h 01m 05s"
+        );
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 3 * 3600 + 4 * 60 + 5)),
+            "2d 3h 04m 05s"
+        );
+    }
+}
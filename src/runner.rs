@@ -0,0 +1,365 @@
+use std::{
+    fmt::Write,
+    fs::read_to_string,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    answers::{Answers, Check},
+    bench::format_duration,
+    client::AocClient,
+    days::{DayResult, Example, Solution},
+    inputs::{self, Download},
+    parser::MyErr,
+    years,
+};
+
+/// How `run` prints its results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Human readable text
+    #[default]
+    Text,
+    /// One JSON record per line for every part that was run
+    Json,
+}
+
+/// How `run` should solve and judge the days.
+#[derive(Default)]
+pub struct RunOptions {
+    /// Verified answers to compare against, when running with `--check`.
+    pub answers: Option<Answers>,
+    /// Where to add the answers of parts without a verified answer, when running with `--record`.
+    pub record: Option<Mutex<Answers>>,
+    /// Run the examples from the puzzle descriptions instead of the real input.
+    pub examples: bool,
+    pub format: Format,
+    /// Downloads missing inputs before solving, unless running offline or without a session.
+    pub client: Option<AocClient>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    /// The part produced an answer that wasn't checked.
+    Solved,
+    Correct,
+    Wrong,
+    /// The part produced an answer but there is no known answer to check it against.
+    New,
+    Failed,
+}
+
+/// The outcome of one part, printed as text or as a JSON record.
+#[derive(Serialize)]
+struct PartReport<'a> {
+    year: usize,
+    day: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    example: Option<&'a str>,
+    part: usize,
+    status: Status,
+    answer: Option<String>,
+    expected: Option<&'a str>,
+    error: Option<String>,
+    read_ns: Option<u128>,
+    parse_ns: Option<u128>,
+    duration_ns: Option<u128>,
+}
+
+impl PartReport<'_> {
+    /// A failing part with a known answer is a regression, just like a wrong answer.
+    fn regressed(&self) -> bool {
+        self.status == Status::Wrong || (self.status == Status::Failed && self.expected.is_some())
+    }
+}
+
+/// Runs every day of `year`, returning whether any of them regressed.
+///
+/// With `parallel` the days are run concurrently on the rayon thread pool. The
+/// output of each day is still printed in one piece and in order of the days,
+/// followed by a summary of the wall time against the time spent on the days.
+pub fn run_all_days(year: usize, options: &RunOptions, parallel: bool) -> bool {
+    if !parallel {
+        return years::solutions(year)
+            .map(|solution| run_day(solution, options))
+            .filter(|&regressed| regressed)
+            .count()
+            > 0;
+    }
+
+    let started = Instant::now();
+    let runs = years::solutions(year)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|solution| {
+            let before = Instant::now();
+            let mut out = String::new();
+            let regressed = write_day(&mut out, solution, options);
+            (out, regressed, before.elapsed())
+        })
+        .collect::<Vec<_>>();
+    let wall = started.elapsed();
+
+    let mut total = Duration::ZERO;
+    for (out, _, elapsed) in &runs {
+        print!("{}", out);
+        total += *elapsed;
+    }
+    let summary = format!(
+        "Ran {} days in {} wall time, {} spent on the days",
+        runs.len(),
+        format_duration(wall),
+        format_duration(total)
+    );
+    match options.format {
+        Format::Text => println!("======== SUMMARY ========\n{}", summary),
+        // Keep stdout a clean stream of JSON records
+        Format::Json => eprintln!("{}", summary),
+    }
+
+    runs.iter().any(|(_, regressed, _)| *regressed)
+}
+
+/// Runs a single day and prints its answers, returning whether it regressed.
+///
+/// When verified answers are given every part is compared against them, and a
+/// wrong or failing part with a verified answer counts as a regression. When
+/// running examples, a part that doesn't produce the example's answer counts as
+/// a regression.
+pub fn run_day(solution: &Solution, options: &RunOptions) -> bool {
+    let mut out = String::new();
+    let regressed = write_day(&mut out, solution, options);
+    print!("{}", out);
+    regressed
+}
+
+/// Runs the examples of a single day and prints how they did, returning whether any failed.
+pub fn run_day_examples(solution: &Solution) -> bool {
+    let mut out = String::new();
+    let options = RunOptions {
+        examples: true,
+        ..Default::default()
+    };
+    let failed = run_examples(&mut out, solution, &options);
+    print!("{}", out);
+    failed
+}
+
+fn write_day(out: &mut String, solution: &Solution, options: &RunOptions) -> bool {
+    if options.format == Format::Text {
+        writeln!(
+            out,
+            "======== DAY {} ({}) ========",
+            solution.day, solution.name
+        )
+        .unwrap();
+    }
+    if options.examples {
+        return run_examples(out, solution, options);
+    }
+    if let Some(client) = &options.client {
+        fetch_missing_input(out, client, solution, options.format);
+    }
+
+    let before_read = Instant::now();
+    let input = read_to_string(solution.input_path());
+    let read = before_read.elapsed();
+    let result = input
+        .map_err(MyErr::from)
+        .and_then(|input| (solution.solve)(&input, &[1, 2]));
+    let expected = |part| {
+        options
+            .answers
+            .as_ref()
+            .map(|answers| answers.get(solution.day, part))
+    };
+    report(out, solution, None, Some(read), result, expected, options)
+}
+
+/// Downloads the input of `solution` if it isn't there yet.
+///
+/// A failed download is only reported; reading the input will fail right after.
+fn fetch_missing_input(out: &mut String, client: &AocClient, solution: &Solution, format: Format) {
+    let message = match inputs::download(client, solution, false) {
+        Ok(Download::Cached(_)) => return,
+        Ok(Download::Saved(path)) => format!("Downloaded missing input to {}", path),
+        Err(e) => format!("Could not download missing input: {}", e),
+    };
+    match format {
+        Format::Text => writeln!(out, "{}", message).unwrap(),
+        Format::Json => eprintln!("{}", message),
+    }
+}
+
+fn run_examples(out: &mut String, solution: &Solution, options: &RunOptions) -> bool {
+    let format = options.format;
+    if solution.examples.is_empty() && format == Format::Text {
+        writeln!(out, "No examples").unwrap();
+    }
+
+    solution
+        .examples
+        .iter()
+        .map(|example| {
+            if format == Format::Text {
+                writeln!(out, "-------- {} --------", example.name).unwrap();
+            }
+            report(
+                out,
+                solution,
+                Some(example),
+                None,
+                (solution.solve)(example.input, &example.parts()),
+                |part| example.expected(part).map(Some),
+                options,
+            )
+        })
+        .filter(|&regressed| regressed)
+        .count()
+        > 0
+}
+
+/// Writes the answers of the parts that were run, returning whether any of them regressed.
+///
+/// For an example only the parts with an expected answer are run. `expected` returns `None` when answers aren't being checked, and
+/// `Some(None)` when they are but the part has no known answer yet. Those
+/// new answers are recorded when running with `--record`.
+fn report<'a>(
+    out: &mut String,
+    solution: &Solution,
+    example: Option<&'a Example>,
+    read: Option<Duration>,
+    result: Result<DayResult, MyErr>,
+    expected: impl Fn(usize) -> Option<Option<&'a str>>,
+    options: &RunOptions,
+) -> bool {
+    let format = options.format;
+    let failed = |part, error: String| PartReport {
+        year: solution.year,
+        day: solution.day,
+        example: example.map(|example| example.name),
+        part,
+        status: Status::Failed,
+        answer: None,
+        expected: expected(part).flatten(),
+        error: Some(error),
+        read_ns: read.map(|read| read.as_nanos()),
+        parse_ns: None,
+        duration_ns: None,
+    };
+
+    let reports = match result {
+        Ok(result) => {
+            if format == Format::Text {
+                if let Some(read) = read {
+                    writeln!(out, "Read took {}", format_duration(read)).unwrap();
+                }
+                writeln!(out, "Parse took {}", format_duration(result.parse)).unwrap();
+            }
+
+            result
+                .parts
+                .into_iter()
+                .map(|part_result| {
+                    let part = part_result.part;
+                    let (status, answer, error) = match part_result.answer {
+                        Ok(answer) => {
+                            let status = match expected(part) {
+                                None => Status::Solved,
+                                Some(expected) => match Check::compare(expected, &answer) {
+                                    Check::Correct => Status::Correct,
+                                    Check::Wrong => Status::Wrong,
+                                    Check::New => Status::New,
+                                },
+                            };
+                            (status, Some(answer), None)
+                        }
+                        Err(e) => (Status::Failed, None, Some(e.to_string())),
+                    };
+                    PartReport {
+                        status,
+                        answer,
+                        error,
+                        parse_ns: Some(result.parse.as_nanos()),
+                        duration_ns: Some(part_result.duration.as_nanos()),
+                        ..failed(part, String::new())
+                    }
+                })
+                .collect::<Vec<_>>()
+        }
+        Err(e) => {
+            if format == Format::Text {
+                writeln!(out, "{}", e).unwrap();
+            }
+            let parts = example.map_or(vec![1, 2], Example::parts);
+            parts
+                .into_iter()
+                .map(|part| failed(part, e.to_string()))
+                .collect()
+        }
+    };
+
+    for report in &reports {
+        if let (Some(record), Status::New, Some(answer)) =
+            (&options.record, report.status, &report.answer)
+        {
+            record
+                .lock()
+                .unwrap()
+                .set(solution.day, report.part, answer);
+        }
+        match format {
+            Format::Text => write_text(out, report),
+            Format::Json => writeln!(out, "{}", serde_json::to_string(report).unwrap()).unwrap(),
+        }
+    }
+
+    reports.iter().any(PartReport::regressed)
+}
+
+fn write_text(out: &mut String, report: &PartReport) {
+    let Some(duration) = report.duration_ns else {
+        // The whole day failed before the part ran, which has already been printed
+        return;
+    };
+
+    match (&report.answer, report.status) {
+        (Some(answer), Status::Solved) => {
+            writeln!(out, "Part {}: {}", report.part, answer).unwrap()
+        }
+        (Some(answer), Status::Correct) => {
+            writeln!(out, "Part {}: {} (correct)", report.part, answer).unwrap()
+        }
+        (Some(answer), Status::New) => {
+            writeln!(out, "Part {}: {} (new)", report.part, answer).unwrap()
+        }
+        (Some(answer), _) => writeln!(
+            out,
+            "Part {}: {} (wrong, expected {})",
+            report.part,
+            answer,
+            report.expected.unwrap_or_default()
+        )
+        .unwrap(),
+        (None, _) => writeln!(
+            out,
+            "Part {} failed: {}",
+            report.part,
+            report.error.as_deref().unwrap_or_default()
+        )
+        .unwrap(),
+    }
+    writeln!(
+        out,
+        "Part {} took {}",
+        report.part,
+        format_duration(Duration::from_nanos(duration as u64))
+    )
+    .unwrap();
+}
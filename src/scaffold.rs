@@ -0,0 +1,328 @@
+use std::{fmt::Display, fs, io, path::Path};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+/// The placeholder days that were hand-copied before there was a template.
+const LEGACY_STUB: &str = "\
+use crate::{
+    days::{Day, SolutionError},
+    parser::ParseResult,
+};
+
+pub struct Day{{DAY}};
+
+impl Day for Day{{DAY}} {
+    type Input = String;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        Ok((\"\", input.to_owned()))
+    }
+
+    type Output1 = usize;
+
+    fn part_1(_input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Err(SolutionError::Unsolved)
+    }
+
+    type Output2 = usize;
+
+    fn part_2(_input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Err(SolutionError::Unsolved)
+    }
+}
+";
+
+/// Why a day could not be scaffolded.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day already has code that is more than a stub.
+    Exists(String),
+    /// A registry doesn't look the way it is expected to, so it can't be edited.
+    Registry(String),
+    Io(io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => {
+                write!(f, "{} already has a solution, not overwriting it", path)
+            }
+            ScaffoldError::Registry(path) => {
+                write!(f, "Could not find where to register the day in {}", path)
+            }
+            ScaffoldError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(e: io::Error) -> ScaffoldError {
+        ScaffoldError::Io(e)
+    }
+}
+
+/// `template` filled in for `day`.
+fn render(template: &str, day: usize) -> String {
+    template.replace("{{DAY}}", &format!("{:02}", day))
+}
+
+/// A day that hasn't been touched since it was generated from the template or hand-copied
+/// as a placeholder. Any edit, even one that leaves both parts unsolved, makes it a solution.
+fn is_stub(source: &str, day: usize) -> bool {
+    [TEMPLATE, LEGACY_STUB]
+        .iter()
+        .any(|template| source == render(template, day))
+}
+
+/// Generates the module of `day` from the template and registers it, creating the year if needed.
+///
+/// Also creates an empty input and example file, unless they already exist.
+/// Returns the paths that were written to.
+pub fn new_day(year: usize, day: usize) -> Result<Vec<String>, ScaffoldError> {
+    let year_dir = format!("src/years/y{}", year);
+    let days_dir = format!("{}/days", year_dir);
+    let module = format!("{}/day{:02}.rs", days_dir, day);
+    if fs::read_to_string(&module).is_ok_and(|source| !is_stub(&source, day)) {
+        return Err(ScaffoldError::Exists(module));
+    }
+
+    let mut written = vec![];
+    if !Path::new(&days_dir).exists() {
+        written.extend(new_year(year, &year_dir, &days_dir)?);
+    }
+
+    fs::write(&module, render(TEMPLATE, day))?;
+    written.push(module);
+
+    let registry = format!("{}/mod.rs", days_dir);
+    if register_day(&registry, day)? && !written.contains(&registry) {
+        written.push(registry);
+    }
+
+    for placeholder in [
+        format!("inputs/{}/day{:02}.txt", year, day),
+        format!("{}/examples/day{:02}-1.txt", days_dir, day),
+    ] {
+        if !Path::new(&placeholder).exists() {
+            fs::create_dir_all(Path::new(&placeholder).parent().unwrap())?;
+            fs::write(&placeholder, "")?;
+            written.push(placeholder);
+        }
+    }
+
+    Ok(written)
+}
+
+/// Adds `dayNN::DayNN = N,` to the `days!` invocation in `registry`, keeping the days ordered.
+///
+/// Returns whether the day had to be added.
+fn register_day(registry: &str, day: usize) -> Result<bool, ScaffoldError> {
+    let source = fs::read_to_string(registry)?;
+    if is_registered(&source, day) {
+        return Ok(false);
+    }
+    let source =
+        add_day(&source, day).ok_or_else(|| ScaffoldError::Registry(registry.to_owned()))?;
+    fs::write(registry, source)?;
+    Ok(true)
+}
+
+fn day_entry(day: usize) -> String {
+    format!("day{:02}::Day{:02} = {},", day, day, day)
+}
+
+fn is_registered(source: &str, day: usize) -> bool {
+    let entry = day_entry(day);
+    source.lines().any(|line| line.trim() == entry)
+}
+
+/// `source` with the entry of `day` inserted before the first later day, or at the end
+/// of the `days!` invocation.
+fn add_day(source: &str, day: usize) -> Option<String> {
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let registered_day = |line: &str| {
+        let (_, number) = line.trim().strip_prefix("day")?.split_once(" = ")?;
+        number.strip_suffix(',')?.parse::<usize>().ok()
+    };
+    let end = lines.iter().rposition(|line| *line == "}")?;
+    let position = lines
+        .iter()
+        .position(|line| registered_day(line).is_some_and(|registered| registered > day))
+        .unwrap_or(end);
+    let line = format!("    {}", day_entry(day));
+    lines.insert(position, &line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Creates the modules of a new year and adds it to the list of years.
+fn new_year(year: usize, year_dir: &str, days_dir: &str) -> Result<Vec<String>, ScaffoldError> {
+    let years = "src/years/mod.rs";
+    let source = add_year(&fs::read_to_string(years)?, year)
+        .ok_or_else(|| ScaffoldError::Registry(years.to_owned()))?;
+
+    fs::create_dir_all(days_dir)?;
+    let year_module = format!("{}/mod.rs", year_dir);
+    fs::write(&year_module, "pub mod days;\n")?;
+    let registry = format!("{}/mod.rs", days_dir);
+    fs::write(
+        &registry,
+        format!(
+            "use crate::days::days;\n\ndays! {{\n    year = {};\n}}\n",
+            year
+        ),
+    )?;
+    fs::write(years, source)?;
+
+    Ok(vec![years.to_owned(), year_module, registry])
+}
+
+/// `source` of `src/years/mod.rs` with the module of `year` declared and its solutions
+/// added to `YEARS`, both in order of the years.
+fn add_year(source: &str, year: usize) -> Option<String> {
+    let list = "const YEARS: &[&[Solution]] = &[";
+    let start = source.find(list)?;
+    let end = start + source[start..].find("];")? + "];".len();
+    let year_of = |name: &str| name.trim_start_matches('y').get(..4)?.parse::<usize>().ok();
+
+    let mut entries = source[start + list.len()..end - "];".len()]
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    entries.push(format!("y{}::days::SOLUTIONS", year));
+    entries.sort_by_key(|entry| year_of(entry));
+    let mut years = format!("{}{}];", list, entries.join(", "));
+    if years.len() > 100 {
+        years = format!("{}\n    {},\n];", list, entries.join(",\n    "));
+    }
+    let source = format!("{}{}{}", &source[..start], years, &source[end..]);
+
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let module = format!("pub mod y{};", year);
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, year_of(line.strip_prefix("pub mod ")?)?)))
+        .collect::<Vec<_>>();
+    let position = match modules.iter().find(|(_, declared)| *declared > year) {
+        Some((i, _)) => *i,
+        None => match modules.last() {
+            Some((i, _)) => i + 1,
+            // Without any years yet, declare it above the documentation of the list
+            None => {
+                let list = lines.iter().position(|line| line.starts_with(list))?;
+                let mut i = list;
+                while i > 0 && lines[i - 1].starts_with("///") {
+                    i -= 1;
+                }
+                lines.insert(i, "");
+                i
+            }
+        },
+    };
+    lines.insert(position, &module);
+    Some(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_day, add_year, is_registered, is_stub, render, TEMPLATE};
+
+    const REGISTRY: &str = "use crate::days::days;
+
+days! {
+    year = 2023;
+    day01::Day01 = 1,
+    day05::Day05 = 5,
+}
+";
+
+    const YEARS: &str = "use crate::days::Solution;
+
+pub mod y2023;
+
+/// The registered solutions of every year, oldest year first.
+const YEARS: &[&[Solution]] = &[y2023::days::SOLUTIONS];
+";
+
+    #[test]
+    fn registers_days_in_order() {
+        let registry = add_day(REGISTRY, 3).unwrap();
+        assert!(
+            registry.contains("day01::Day01 = 1,\n    day03::Day03 = 3,\n    day05::Day05 = 5,\n}")
+        );
+        let registry = add_day(&registry, 25).unwrap();
+        assert!(registry.ends_with("day05::Day05 = 5,\n    day25::Day25 = 25,\n}\n"));
+        let registry = add_day("days! {\n    year = 2024;\n}\n", 7).unwrap();
+        assert_eq!(
+            registry,
+            "days! {\n    year = 2024;\n    day07::Day07 = 7,\n}\n"
+        );
+    }
+
+    #[test]
+    fn only_overwrites_untouched_stubs() {
+        assert!(is_stub(&render(TEMPLATE, 14), 14));
+        assert!(!is_stub(&render(TEMPLATE, 14), 15));
+        assert!(is_stub(include_str!("years/y2023/days/day13.rs"), 13));
+        assert!(!is_stub(include_str!("years/y2023/days/day12.rs"), 12));
+
+        // A parser of its own is a start, even while both parts are unsolved
+        let started = render(TEMPLATE, 14).replace("Vec<String>", "Vec<Vec<char>>");
+        assert!(started.contains("Err(SolutionError::Unsolved)"));
+        assert!(!is_stub(&started, 14));
+    }
+
+    #[test]
+    fn knows_which_days_are_registered() {
+        assert!(is_registered(REGISTRY, 5));
+        assert!(!is_registered(REGISTRY, 3));
+        assert!(!is_registered(REGISTRY, 15));
+    }
+
+    #[test]
+    fn adds_years_in_order() {
+        assert_eq!(
+            add_year(YEARS, 2024).unwrap(),
+            YEARS
+                .replace("y2023;\n", "y2023;\npub mod y2024;\n")
+                .replace(
+                    "[y2023::days::SOLUTIONS]",
+                    "[y2023::days::SOLUTIONS, y2024::days::SOLUTIONS]"
+                )
+        );
+        assert_eq!(
+            add_year(YEARS, 2019).unwrap(),
+            YEARS
+                .replace("pub mod y2023;", "pub mod y2019;\npub mod y2023;")
+                .replace(
+                    "[y2023::days::SOLUTIONS]",
+                    "[y2019::days::SOLUTIONS, y2023::days::SOLUTIONS]"
+                )
+        );
+    }
+
+    #[test]
+    fn adds_the_first_year() {
+        assert_eq!(
+            add_year(
+                "use crate::days::Solution;\n\n/// Every year.\nconst YEARS: &[&[Solution]] = &[];\n",
+                2015
+            )
+            .unwrap(),
+            "use crate::days::Solution;\n\npub mod y2015;\n\n/// Every year.\nconst YEARS: &[&[Solution]] = &[y2015::days::SOLUTIONS];\n"
+        );
+    }
+
+    #[test]
+    fn wraps_long_lists_of_years() {
+        let years = (2016..=2018).rev().fold(YEARS.to_owned(), |years, year| {
+            add_year(&years, year).unwrap()
+        });
+        assert!(years.contains(
+            "const YEARS: &[&[Solution]] = &[\n    y2016::days::SOLUTIONS,\n    y2017::days::SOLUTIONS,\n    y2018::days::SOLUTIONS,\n    y2023::days::SOLUTIONS,\n];\n"
+        ));
+        assert_eq!(add_year(&years, 2024).unwrap().matches("y2024").count(), 2);
+    }
+}
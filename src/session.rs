@@ -0,0 +1,103 @@
+use std::{env, fmt::Display, fs, path::PathBuf};
+
+use crate::dirs;
+
+/// Where the session cookie was found.
+#[derive(Debug)]
+pub enum Source {
+    Env,
+    File(PathBuf),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Env => write!(f, "the AOC_SESSION environment variable"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The session cookie that authenticates requests to Advent of Code.
+pub struct Session {
+    pub cookie: String,
+    pub source: Source,
+}
+
+/// Finds the session cookie in `AOC_SESSION`, `.session` or the user config file, in that order.
+pub fn find() -> Option<Session> {
+    find_in(
+        env::var("AOC_SESSION").ok(),
+        [
+            Some(PathBuf::from(".session")),
+            dirs::config_dir().map(|dir| dir.join("session")),
+        ]
+        .into_iter()
+        .flatten(),
+    )
+}
+
+/// The cookie in `env` if it is set, or else the one in the first of `files` that has one.
+fn find_in(env: Option<String>, files: impl IntoIterator<Item = PathBuf>) -> Option<Session> {
+    if let Some(cookie) = env.filter(|cookie| !cookie.trim().is_empty()) {
+        return Some(Session {
+            cookie: cookie.trim().to_owned(),
+            source: Source::Env,
+        });
+    }
+
+    files.into_iter().find_map(|path| {
+        let cookie = fs::read_to_string(&path).ok()?;
+        let cookie = cookie.trim();
+        (!cookie.is_empty()).then(|| Session {
+            cookie: cookie.to_owned(),
+            source: Source::File(path),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::{find_in, Source};
+
+    /// Session files with the given contents in a fresh directory, with a missing one last.
+    fn session_files(name: &str, cookies: &[&str]) -> Vec<PathBuf> {
+        let dir = env::temp_dir().join(format!("aoc-session-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let mut files = cookies
+            .iter()
+            .enumerate()
+            .map(|(i, cookie)| {
+                let path = dir.join(i.to_string());
+                fs::write(&path, cookie).unwrap();
+                path
+            })
+            .collect::<Vec<_>>();
+        files.push(dir.join("missing"));
+        files
+    }
+
+    #[test]
+    fn prefers_the_environment_variable() {
+        let files = session_files("env", &["from-file"]);
+        let session = find_in(Some(" from-env\n".to_owned()), files).unwrap();
+        assert_eq!(session.cookie, "from-env");
+        assert!(matches!(session.source, Source::Env));
+    }
+
+    #[test]
+    fn falls_back_to_the_first_file_with_a_cookie() {
+        let files = session_files("files", &["", "  second\n", "third"]);
+        let session = find_in(Some(" ".to_owned()), files.clone()).unwrap();
+        assert_eq!(session.cookie, "second");
+        assert!(matches!(session.source, Source::File(path) if path == files[1]));
+    }
+
+    #[test]
+    fn finds_nothing_without_a_cookie() {
+        assert!(find_in(None, session_files("none", &["\n"])).is_none());
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    env, fs,
+    path::Path,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use chrono::Local;
+
+use crate::{bench::format_duration, days::Solution, parser::MyErr, runner};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Carries the answers over to the rebuilt binary, so it can show what changed.
+const PREVIOUS_ANSWERS: &str = "AOC_WATCH_ANSWERS";
+
+/// Runs `solution` whenever its input changes, and rebuilds and restarts when the source changes.
+///
+/// Every run checks the examples, which are compiled in, against their expected answers
+/// first, and then shows how the answers to the input differ from the previous run. Never returns;
+/// stop watching with Ctrl-C.
+pub fn watch(solution: &Solution) -> ! {
+    // Looked up before rebuilding, as the path of a replaced binary reads as deleted
+    let exe = env::current_exe().unwrap();
+    let input_path = solution.input_path();
+    let mut input_modified = modified(Path::new(&input_path));
+    let mut source_modified = newest_in(Path::new("src"));
+    let mut previous = env::var(PREVIOUS_ANSWERS)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    println!(
+        "Watching {} and src/ for changes, press Ctrl-C to stop",
+        input_path
+    );
+    previous = run(solution, &previous);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let source = newest_in(Path::new("src"));
+        if source != source_modified {
+            source_modified = source;
+            println!(
+                "[{}] Source changed, rebuilding",
+                Local::now().format("%H:%M:%S")
+            );
+            if rebuild() {
+                restart(&exe, &previous);
+            }
+            continue;
+        }
+
+        let input = modified(Path::new(&input_path));
+        if input != input_modified {
+            input_modified = input;
+            println!("[{}] Input changed", Local::now().format("%H:%M:%S"));
+            previous = run(solution, &previous);
+        }
+    }
+}
+
+/// Runs the examples, then solves the input and prints the answers next to the previous ones.
+///
+/// Examples only run the parts they have an answer for, and each part of the input is
+/// printed as soon as it is solved, so a slow part 2 doesn't hide the answer to part 1.
+fn run(solution: &Solution, previous: &[Option<String>; 2]) -> [Option<String>; 2] {
+    runner::run_day_examples(solution);
+    println!("-------- input --------");
+    let input = match fs::read_to_string(solution.input_path()) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("{}", MyErr::from(e));
+            return previous.clone();
+        }
+    };
+
+    let mut answers = previous.clone();
+    for part in 1..=2 {
+        let part_result = match (solution.solve)(&input, &[part]) {
+            Ok(mut result) => result.parts.remove(0),
+            Err(e) => {
+                println!("{}", e);
+                return previous.clone();
+            }
+        };
+        let answer = match part_result.answer {
+            Ok(answer) => answer,
+            Err(e) => {
+                println!("Part {} failed: {}", part, e);
+                continue;
+            }
+        };
+        let change = match &previous[part - 1] {
+            None => String::new(),
+            Some(before) if *before == answer => " (unchanged)".to_owned(),
+            Some(before) => format!(" (was {})", before),
+        };
+        println!(
+            "Part {}: {}{} in {}",
+            part,
+            answer,
+            change,
+            format_duration(part_result.duration)
+        );
+        answers[part - 1] = Some(answer);
+    }
+    answers
+}
+
+/// Builds the binary with the profile it was built with, returning whether that succeeded.
+fn rebuild() -> bool {
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()));
+    cargo.arg("build");
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    cargo.status().is_ok_and(|status| status.success())
+}
+
+/// Replaces this process with the freshly built binary, watching with the same arguments.
+fn restart(exe: &Path, answers: &[Option<String>; 2]) {
+    let mut command = Command::new(exe);
+    command
+        .args(env::args_os().skip(1))
+        .env(PREVIOUS_ANSWERS, serde_json::to_string(answers).unwrap());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let e = command.exec();
+        println!("Could not restart: {}", e);
+    }
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => println!("Could not restart: {}", e),
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The latest modification time of any file in `dir` and its subdirectories.
+fn newest_in(dir: &Path) -> Option<SystemTime> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                newest_in(&path)
+            } else {
+                modified(&path)
+            }
+        })
+        .max()
+}
@@ -0,0 +1,29 @@
+use crate::days::Solution;
+
+pub mod y2023;
+
+/// The registered solutions of every year, oldest year first.
+const YEARS: &[&[Solution]] = &[y2023::days::SOLUTIONS];
+
+/// Iterates over the registered solutions of `year`.
+pub fn solutions(year: usize) -> impl Iterator<Item = &'static Solution> {
+    YEARS
+        .iter()
+        .flat_map(|solutions| solutions.iter())
+        .filter(move |solution| solution.year == year)
+}
+
+/// Looks up the registered solution for `day` of `year`.
+pub fn find(year: usize, day: usize) -> Option<&'static Solution> {
+    solutions(year).find(|solution| solution.day == day)
+}
+
+/// The most recent year that has registered solutions.
+pub fn latest_year() -> usize {
+    YEARS
+        .iter()
+        .flat_map(|solutions| solutions.iter())
+        .map(|solution| solution.year)
+        .max()
+        .expect("at least one registered year")
+}
@@ -5,27 +5,38 @@ use nom::{
     combinator::{iterator, map, map_res, peek, value},
     multi::many_till,
     sequence::pair,
-    IResult,
 };
 
-use crate::days::Day;
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    parser::ParseResult,
+};
 
 pub struct Day01;
 
+examples! {
+    Day01 {
+        example_1: "examples/day01-1.txt" => { part_1: "142" },
+        example_2: "examples/day01-2.txt" => { part_2: "281" },
+    }
+}
+
 impl Day for Day01 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
     type Input = Vec<String>;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
         Ok((
-            input,
+            "",
             input.trim().split('\n').map(ToString::to_string).collect(),
         ))
     }
 
     type Output1 = u32;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        fn to_digit(input: &str) -> IResult<&str, u32> {
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        fn to_digit(input: &str) -> ParseResult<'_, u32> {
             map_res(anychar, |c| c.to_digit(10).ok_or(()))(input)
         }
 
@@ -34,8 +45,8 @@ impl Day for Day01 {
 
     type Output2 = u32;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        fn to_digit(input: &str) -> IResult<&str, u32> {
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        fn to_digit(input: &str) -> ParseResult<'_, u32> {
             alt((
                 map_res(anychar, |c| c.to_digit(10).ok_or(())),
                 value(1, pair(peek(tag("one")), anychar)),
@@ -54,16 +65,21 @@ impl Day for Day01 {
     }
 }
 
-fn calc(input: &Vec<String>, to_digit: fn(&str) -> IResult<&str, u32>) -> u32 {
+fn calc(
+    input: &[String],
+    to_digit: fn(&str) -> ParseResult<'_, u32>,
+) -> Result<u32, SolutionError> {
     input
         .iter()
         .map(AsRef::as_ref)
         .map(|row| {
             let mut digits = iterator(row, map(many_till(anychar, to_digit), |(_, v)| v));
             let mut digits = digits.into_iter();
-            let first = digits.next().expect("first digit");
+            let first = digits
+                .next()
+                .ok_or_else(|| SolutionError::InvalidInput(format!("No digit in {row:?}")))?;
             let last = digits.last().unwrap_or(first);
-            first * 10 + last
+            Ok(first * 10 + last)
         })
         .sum()
 }
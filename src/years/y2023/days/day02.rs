@@ -1,14 +1,16 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{multispace0, newline},
     combinator::map,
     multi::separated_list1,
     sequence::{preceded, tuple},
-    IResult,
 };
 
-use crate::{days::Day, helpers::parse_digit};
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::{lines_of, parse_digit},
+    parser::ParseResult,
+};
 
 pub struct Day02;
 
@@ -25,22 +27,24 @@ pub struct Round {
     blue: u32,
 }
 
+examples! {
+    Day02 {
+        example: "examples/day02.txt" => { part_1: "8", part_2: "2286" },
+    }
+}
+
 impl Day for Day02 {
-    type Input = Vec<Game>;
+    const EXAMPLES: &'static [Example] = EXAMPLES;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        // let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-        // Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-        // Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-        // Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-        // Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+    type Input = Vec<Game>;
 
-        separated_list1(newline, parse_game)(input)
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        lines_of(parse_game)(input)
     }
 
     type Output1 = u32;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
         // println!("{:?}", input);
         let red = 12;
         let green = 13;
@@ -48,48 +52,48 @@ impl Day for Day02 {
         let mut sum = 0;
 
         for game in input {
-            let max_red = game.rounds.iter().max_by_key(|r| r.red).unwrap().red;
-            let max_green = game.rounds.iter().max_by_key(|r| r.green).unwrap().green;
-            let max_blue = game.rounds.iter().max_by_key(|r| r.blue).unwrap().blue;
+            let max_red = game.rounds.iter().map(|r| r.red).max().unwrap_or(0);
+            let max_green = game.rounds.iter().map(|r| r.green).max().unwrap_or(0);
+            let max_blue = game.rounds.iter().map(|r| r.blue).max().unwrap_or(0);
 
             if max_red <= red && max_green <= green && max_blue <= blue {
                 sum += game.id;
             }
         }
 
-        sum
+        Ok(sum)
     }
 
     type Output2 = u32;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
         let mut sum = 0;
 
         for game in input {
-            let max_red = game.rounds.iter().max_by_key(|r| r.red).unwrap().red;
-            let max_green = game.rounds.iter().max_by_key(|r| r.green).unwrap().green;
-            let max_blue = game.rounds.iter().max_by_key(|r| r.blue).unwrap().blue;
+            let max_red = game.rounds.iter().map(|r| r.red).max().unwrap_or(0);
+            let max_green = game.rounds.iter().map(|r| r.green).max().unwrap_or(0);
+            let max_blue = game.rounds.iter().map(|r| r.blue).max().unwrap_or(0);
 
             let power = max_red * max_green * max_blue;
 
             sum += power;
         }
 
-        sum
+        Ok(sum)
     }
 }
 
-fn parse_game(input: &str) -> IResult<&str, Game> {
+fn parse_game(input: &str) -> ParseResult<'_, Game> {
     map(
         tuple((
-            preceded(preceded(multispace0, tag("Game ")), parse_digit("game id")),
+            preceded(tag("Game "), parse_digit("game id")),
             preceded(tag(": "), separated_list1(tag("; "), parse_round)),
         )),
         |(id, rounds)| Game { id, rounds },
     )(input)
 }
 
-fn parse_round(input: &str) -> IResult<&str, Round> {
+fn parse_round(input: &str) -> ParseResult<'_, Round> {
     map(
         separated_list1(
             tag(", "),
@@ -5,10 +5,13 @@ use nom::{
     character::complete::{anychar, char, line_ending},
     combinator::map,
     multi::many1,
-    IResult,
 };
 
-use crate::{days::Day, helpers::parse_digit};
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::parse_digit,
+    parser::ParseResult,
+};
 
 #[derive(Debug, PartialEq)]
 pub enum Token {
@@ -29,22 +32,18 @@ impl Token {
 
 pub struct Day03;
 
+examples! {
+    Day03 {
+        example: "examples/day03.txt" => { part_1: "4361", part_2: "467835" },
+    }
+}
+
 impl Day for Day03 {
-    type Input = BTreeMap<(i32, i32), (char, Vec<((i32, i32), u32)>)>;
+    const EXAMPLES: &'static [Example] = EXAMPLES;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        // let input = "
-        // 467..114..
-        // ...*......
-        // ..35..633.
-        // ......#...
-        // 617*......
-        // .....+.58.
-        // ..592.....
-        // ......755.
-        // ...$.*....
-        // .664.598..";
+    type Input = BTreeMap<(i32, i32), (char, Vec<((i32, i32), u32)>)>;
 
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
         map(
             many1(alt((
                 map(parse_digit("number"), Token::Number),
@@ -102,21 +101,20 @@ impl Day for Day03 {
 
     type Output1 = u32;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Ok(input
             .values()
             .flat_map(|(_, nums)| nums)
             .copied()
             .collect::<BTreeMap<(i32, i32), u32>>()
             .values()
-            .sum()
-        // 4361
+            .sum())
     }
 
     type Output2 = u32;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        input
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Ok(input
             .values()
             .filter_map(|(part, nums)| -> Option<u32> {
                 if *part == '*' && nums.len() == 2 {
@@ -125,7 +123,6 @@ impl Day for Day03 {
                     None
                 }
             })
-            .sum()
-        //467835
+            .sum())
     }
 }
@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use nom::combinator::map;
+
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::lines_of,
+    parser::{Parse, ParseResult},
+};
+
+pub struct Day04;
+
+#[derive(Clone, Debug, Parse)]
+#[parse("Card {id}: {winning: list< >} | {selected: list< >}")]
+pub struct Card {
+    id: usize,
+    winning: Vec<usize>,
+    selected: Vec<usize>,
+}
+
+examples! {
+    Day04 {
+        example: "examples/day04.txt" => { part_1: "13", part_2: "30" },
+    }
+}
+
+impl Day for Day04 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
+    type Input = Vec<Rc<Card>>;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        lines_of(map(Card::parse, Rc::new))(input)
+    }
+
+    type Output1 = usize;
+
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Ok(input
+            .iter()
+            .map(|card| {
+                let mut points = 0;
+
+                for n in card.selected.iter() {
+                    if card.winning.contains(n) {
+                        if points == 0 {
+                            points = 1;
+                        } else {
+                            points *= 2;
+                        }
+                    }
+                }
+
+                points
+            })
+            .sum())
+    }
+
+    type Output2 = usize;
+
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        let mut scores = vec![None; input.len()];
+        let mut cards = input.clone();
+        let mut total = cards.len();
+
+        while let Some(card) = cards.pop() {
+            let matching = *scores[card.id - 1].get_or_insert_with(|| {
+                card.selected
+                    .iter()
+                    .filter(|n| card.winning.contains(n))
+                    .count()
+            });
+            for id in (card.id)..(card.id + matching) {
+                if let Some(card) = input.get(id) {
+                    cards.push(card.clone());
+                    total += 1;
+                }
+            }
+        }
+
+        Ok(total)
+    }
+}
@@ -0,0 +1,203 @@
+use std::{cmp::Ordering, ops::Range, slice::Iter};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{line_ending, space1},
+    combinator::map,
+    error::context,
+    sequence::{preceded, tuple},
+};
+
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::{blank_lines, labelled, lines_of, number_list, parse_digit},
+    parser::ParseResult,
+};
+
+/// Source ranges sorted by their start, with the destination each one starts at.
+type Map = Vec<(Range<usize>, usize)>;
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<usize>,
+    seed_to_soil: Map,
+    soil_to_fertilizer: Map,
+    fertilizer_to_water: Map,
+    water_to_light: Map,
+    light_to_temperature: Map,
+    temperature_to_humidity: Map,
+    humidity_to_location: Map,
+}
+
+impl Almanac {
+    fn lookup(&self, key: usize, map: &[(Range<usize>, usize)]) -> usize {
+        map.binary_search_by(|(src, _dst)| {
+            if src.contains(&key) {
+                Ordering::Equal
+            } else {
+                src.start.cmp(&key)
+            }
+        })
+        .map_or(key, |i| {
+            let (ref src, dst) = map[i];
+            dst + key - src.start
+        })
+    }
+
+    fn multi_lookup(&self, key: usize, maps: &[&[(Range<usize>, usize)]]) -> usize {
+        maps.iter().fold(key, |key, map| self.lookup(key, map))
+    }
+
+    fn seed_to_location(&self, seed: usize) -> usize {
+        self.multi_lookup(
+            seed,
+            &[
+                &self.seed_to_soil,
+                &self.soil_to_fertilizer,
+                &self.fertilizer_to_water,
+                &self.water_to_light,
+                &self.light_to_temperature,
+                &self.temperature_to_humidity,
+                &self.humidity_to_location,
+            ],
+        )
+    }
+
+    fn seeds(&self) -> SeedsIter<'_> {
+        SeedsIter {
+            seeds: self.seeds.iter(),
+            range: None,
+        }
+    }
+}
+
+struct SeedsIter<'a> {
+    seeds: Iter<'a, usize>,
+    range: Option<Range<usize>>,
+}
+
+impl<'a> Iterator for SeedsIter<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.range.as_mut().and_then(|range| range.next());
+
+        if next.is_some() {
+            return next;
+        };
+
+        if let (Some(start), Some(len)) = (self.seeds.next(), self.seeds.next()) {
+            self.range = Some(*start..(start + len))
+        }
+
+        self.range.as_mut().and_then(|range| range.next())
+    }
+}
+
+pub struct Day05;
+
+examples! {
+    Day05 {
+        example: "examples/day05.txt" => { part_1: "35", part_2: "46" },
+    }
+}
+
+impl Day for Day05 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
+    type Input = Almanac;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        context(
+            "almanac with all seven maps",
+            map(
+                tuple((
+                    context("seeds", labelled(tag("seeds"), number_list("seed"))),
+                    parse_section("seed-to-soil map"),
+                    parse_section("soil-to-fertilizer map"),
+                    parse_section("fertilizer-to-water map"),
+                    parse_section("water-to-light map"),
+                    parse_section("light-to-temperature map"),
+                    parse_section("temperature-to-humidity map"),
+                    parse_section("humidity-to-location map"),
+                )),
+                |(
+                    (_, seeds),
+                    seed_to_soil,
+                    soil_to_fertilizer,
+                    fertilizer_to_water,
+                    water_to_light,
+                    light_to_temperature,
+                    temperature_to_humidity,
+                    humidity_to_location,
+                )| Almanac {
+                    seeds,
+                    seed_to_soil,
+                    soil_to_fertilizer,
+                    fertilizer_to_water,
+                    water_to_light,
+                    light_to_temperature,
+                    temperature_to_humidity,
+                    humidity_to_location,
+                },
+            ),
+        )(input)
+    }
+
+    type Output1 = usize;
+
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        input
+            .seeds
+            .iter()
+            .map(|seed| input.seed_to_location(*seed))
+            .min()
+            .ok_or(SolutionError::NoAnswer)
+    }
+
+    type Output2 = usize;
+
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        input
+            .seeds()
+            .map(|seed| input.seed_to_location(seed))
+            .min()
+            .ok_or(SolutionError::NoAnswer)
+    }
+}
+
+/// The map headed by `name:` after a blank line, so a missing, repeated or misspelled map
+/// fails where its header should be.
+fn parse_section<'a>(name: &'static str) -> impl FnMut(&'a str) -> ParseResult<'a, Map> {
+    context(
+        name,
+        map(
+            preceded(
+                blank_lines,
+                labelled(tag(name), preceded(line_ending, parse_map)),
+            ),
+            |(_, map)| map,
+        ),
+    )
+}
+
+fn parse_map(input: &str) -> ParseResult<'_, Map> {
+    map(
+        lines_of(tuple((
+            parse_digit("dst start"),
+            preceded(space1, parse_digit("src start")),
+            preceded(space1, parse_digit("range length")),
+        ))),
+        |mut ranges: Vec<(usize, usize, usize)>| {
+            let mut map = vec![];
+
+            ranges.sort_by_key(|(_dst_start, src_start, _length)| *src_start);
+
+            for (dst_start, src_start, length) in ranges {
+                map.push((src_start..(src_start + length), dst_start));
+            }
+
+            map
+        },
+    )(input)
+}
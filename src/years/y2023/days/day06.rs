@@ -0,0 +1,97 @@
+use std::num::ParseIntError;
+
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, multispace0, multispace1},
+    combinator::{map_res, verify},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    parser::ParseResult,
+};
+
+#[derive(Debug)]
+pub struct Race {
+    time: usize,
+    distance: usize,
+}
+
+impl Race {
+    fn distance(&self, button_time: usize) -> usize {
+        (self.time - button_time) * button_time
+    }
+
+    fn winning_moves<'a>(&'a self) -> impl ParallelIterator<Item = (usize, usize)> + 'a {
+        (1..self.time)
+            .into_par_iter()
+            .map(|button_time| (button_time, self.distance(button_time)))
+            .filter(|(_time, distance)| *distance > self.distance)
+    }
+}
+
+pub struct Day06;
+
+examples! {
+    Day06 {
+        example: "examples/day06.txt" => { part_1: "288", part_2: "71503" },
+    }
+}
+
+impl Day for Day06 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
+    type Input = (Vec<Race>, Race);
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        map_res(
+            verify(
+                tuple((
+                    preceded(
+                        delimited(multispace0, tag("Time:"), multispace1),
+                        separated_list1(multispace1, digit1),
+                    ),
+                    preceded(
+                        delimited(multispace0, tag("Distance:"), multispace1),
+                        separated_list1(multispace1, digit1),
+                    ),
+                )),
+                |(times, distances): &(Vec<&str>, Vec<&str>)| times.len() == distances.len(),
+            ),
+            |(times, distances)| -> Result<_, ParseIntError> {
+                let races = times
+                    .iter()
+                    .zip_eq(distances.iter())
+                    .map(|(time, distance)| {
+                        Ok(Race {
+                            time: time.parse()?,
+                            distance: distance.parse()?,
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                let time = times.join("").parse()?;
+                let distance = distances.join("").parse()?;
+                Ok((races, Race { time, distance }))
+            },
+        )(input)
+    }
+
+    type Output1 = usize;
+
+    fn part_1((races, _): &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Ok(races
+            .iter()
+            .map(|race| race.winning_moves().count())
+            .product())
+    }
+
+    type Output2 = usize;
+
+    fn part_2((_, race): &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Ok(race.winning_moves().count())
+    }
+}
@@ -2,14 +2,17 @@ use std::{cmp::Ordering, collections::BTreeMap};
 
 use itertools::Itertools;
 use nom::{
-    character::complete::{alphanumeric1, line_ending, multispace0, multispace1},
-    combinator::{all_consuming, map},
-    multi::separated_list1,
+    character::complete::{one_of, space1},
+    combinator::{map, map_res},
+    multi::count,
     sequence::{preceded, tuple},
-    IResult,
 };
 
-use crate::{days::Day, helpers::parse_digit};
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::{lines_of, parse_digit},
+    parser::ParseResult,
+};
 
 const CARDS: [char; 14] = [
     'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2', '*',
@@ -26,7 +29,7 @@ pub enum Score {
     High,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Hand([char; 5], usize);
 
 impl Hand {
@@ -76,7 +79,13 @@ impl Hand {
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.score().cmp(&other.score()).then_with(|| {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score().cmp(&other.score()).then_with(|| {
             self.0
                 .iter()
                 .zip_eq(other.0.iter())
@@ -88,39 +97,38 @@ impl PartialOrd for Hand {
                         a.cmp(&b)
                     })
                 })
-        }))
+        })
     }
 }
 
 pub struct Day07;
 
+examples! {
+    Day07 {
+        example: "examples/day07.txt" => { part_1: "6440", part_2: "5905" },
+    }
+}
+
 impl Day for Day07 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
     type Input = Vec<Hand>;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        // let input = "32T3K 765
-        // T55J5 684
-        // KK677 28
-        // KTJJT 220
-        // QQQJA 483";
-
-        all_consuming(separated_list1(
-            preceded(line_ending, multispace0),
-            map(
-                tuple((alphanumeric1, preceded(multispace1, parse_digit("bid")))),
-                |(cards, bid): (&str, usize)| {
-                    Hand(
-                        cards.chars().collect_vec().try_into().expect("5 cards"),
-                        bid,
-                    )
-                },
-            ),
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        lines_of(map(
+            tuple((
+                map_res(count(one_of("AKQJT98765432"), 5), |cards: Vec<char>| {
+                    cards.try_into()
+                }),
+                preceded(space1, parse_digit("bid")),
+            )),
+            |(cards, bid)| Hand(cards, bid),
         ))(input)
     }
 
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
         let mut input = input.clone();
         input.sort();
 
@@ -131,12 +139,12 @@ impl Day for Day07 {
             winnings += hand.1 * rank;
         }
 
-        winnings
+        Ok(winnings)
     }
 
     type Output2 = usize;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
         let mut input = input.clone();
         for hand in input.iter_mut() {
             hand.make_jokers();
@@ -150,6 +158,6 @@ impl Day for Day07 {
             winnings += hand.1 * rank;
         }
 
-        winnings
+        Ok(winnings)
     }
 }
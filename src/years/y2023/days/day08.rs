@@ -3,35 +3,49 @@ use std::cell::RefCell;
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
-    character::complete::{alphanumeric1, line_ending, multispace0, one_of},
-    combinator::map,
-    multi::{many1, separated_list1},
+    branch::alt,
+    character::complete::{alphanumeric1, char, line_ending},
+    combinator::{map, value},
+    multi::many1,
     sequence::{delimited, preceded, terminated, tuple},
-    IResult,
 };
 use num::integer::lcm;
 
-use crate::days::Day;
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::lines_of,
+    parser::ParseResult,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub enum Step {
+    Left,
+    Right,
+}
 
 #[derive(Debug)]
 pub struct Data {
-    steps: Vec<char>,
+    steps: Vec<Step>,
     names: Vec<String>,
     nodes: Vec<(usize, usize)>,
 }
 
 pub struct Day08;
 
-impl Day for Day08 {
-    type Input = Data;
+examples! {
+    Day08 {
+        example_1: "examples/day08-1.txt" => { part_1: "2" },
+        example_2: "examples/day08-2.txt" => { part_1: "6" },
+        example_3: "examples/day08-3.txt" => { part_2: "6" },
+    }
+}
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        // let input = "LLR
+impl Day for Day08 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
 
-        // AAA = (BBB, BBB)
-        // BBB = (AAA, ZZZ)
-        // ZZZ = (ZZZ, ZZZ)";
+    type Input = Data;
 
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
         let names = RefCell::new(vec![]);
         fn parse_node<'a>(names: &'a RefCell<Vec<String>>) -> impl FnMut(&str) -> usize + 'a {
             |node: &str| {
@@ -46,11 +60,15 @@ impl Day for Day08 {
         }
 
         let (input, (steps, mut nodes)) = tuple((
-            terminated(many1(one_of("RL")), many1(line_ending)),
-            separated_list1(
-                preceded(line_ending, multispace0),
-                tuple((
-                    preceded(multispace0, map(alphanumeric1, parse_node(&names))),
+            terminated(
+                many1(alt((
+                    value(Step::Left, char('L')),
+                    value(Step::Right, char('R')),
+                ))),
+                many1(line_ending),
+            ),
+            lines_of(tuple((
+                    map(alphanumeric1, parse_node(&names)),
                     preceded(
                         tag(" = ("),
                         tuple((
@@ -75,18 +93,25 @@ impl Day for Day08 {
 
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        let start = input.names.iter().position(|n| n == "AAA").expect("start");
-        let stop = input.names.iter().position(|n| n == "ZZZ").expect("stop");
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        let start = input
+            .names
+            .iter()
+            .position(|n| n == "AAA")
+            .ok_or_else(|| SolutionError::InvalidInput("No AAA node".into()))?;
+        let stop = input
+            .names
+            .iter()
+            .position(|n| n == "ZZZ")
+            .ok_or_else(|| SolutionError::InvalidInput("No ZZZ node".into()))?;
 
         let steps = input.steps.iter().cycle();
         let mut current = start;
         let mut count = 0;
         for step in steps {
             match step {
-                'L' => current = input.nodes[current].0,
-                'R' => current = input.nodes[current].1,
-                _ => unreachable!(),
+                Step::Left => current = input.nodes[current].0,
+                Step::Right => current = input.nodes[current].1,
             }
             count += 1;
             if current == stop {
@@ -94,12 +119,12 @@ impl Day for Day08 {
             }
         }
 
-        count
+        Ok(count)
     }
 
     type Output2 = usize;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
         let starts = input
             .names
             .iter()
@@ -112,9 +137,8 @@ impl Day for Day08 {
             let mut count = 0;
             for step in steps {
                 match step {
-                    'L' => current = input.nodes[current].0,
-                    'R' => current = input.nodes[current].1,
-                    _ => unreachable!(),
+                    Step::Left => current = input.nodes[current].0,
+                    Step::Right => current = input.nodes[current].1,
                 }
                 count += 1;
 
@@ -125,6 +149,6 @@ impl Day for Day08 {
             count
         });
 
-        start_counts.reduce(lcm).unwrap()
+        start_counts.reduce(lcm).ok_or(SolutionError::NoAnswer)
     }
 }
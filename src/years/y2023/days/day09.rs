@@ -0,0 +1,75 @@
+use itertools::Itertools;
+
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::{lines_of, number_list},
+    parser::ParseResult,
+};
+
+pub struct Day09;
+
+examples! {
+    Day09 {
+        example: "examples/day09.txt" => { part_1: "114", part_2: "2" },
+    }
+}
+
+impl Day for Day09 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
+    type Input = Vec<Vec<isize>>;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        lines_of(number_list("num"))(input)
+    }
+
+    type Output1 = isize;
+
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Ok(input.iter().map(|row| extrapolate_end(row)).sum())
+    }
+
+    type Output2 = isize;
+
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Ok(input.iter().map(|row| extrapolate_start(row)).sum())
+    }
+}
+
+fn extrapolate_end(row: &[isize]) -> isize {
+    let differences = calculate_differences(row);
+
+    let next_difference = if differences.iter().all(|n| *n == 0) {
+        0
+    } else {
+        extrapolate_end(&differences)
+    };
+
+    row.last().unwrap() + next_difference
+}
+
+fn extrapolate_start(row: &[isize]) -> isize {
+    let differences = calculate_differences(row);
+
+    let previous_difference = if differences.iter().all(|n| *n == 0) {
+        0
+    } else {
+        extrapolate_start(&differences)
+    };
+
+    row.first().unwrap() - previous_difference
+}
+
+fn calculate_differences(row: &[isize]) -> Vec<isize> {
+    let mut row_iter = row.iter();
+    let first = row_iter.next().copied();
+    row_iter
+        .scan(first, |prev, curr| {
+            prev.as_mut().map(|prev| {
+                let val = *curr - *prev;
+                *prev = *curr;
+                val
+            })
+        })
+        .collect_vec()
+}
@@ -3,13 +3,15 @@ use std::{collections::BTreeSet, fmt::Display};
 use itertools::Itertools;
 use nom::{
     branch::alt,
-    character::complete::{char, multispace1},
+    character::complete::char,
     combinator::value,
-    multi::{many1, separated_list1},
-    IResult,
 };
 
-use crate::days::Day;
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::grid_of,
+    parser::ParseResult,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Tile {
@@ -26,31 +28,19 @@ pub enum Tile {
 
 impl Tile {
     fn connects_north(self) -> bool {
-        match self {
-            Tile::Vertical | Tile::NorthWest | Tile::NorthEast => true,
-            _ => false,
-        }
+        matches!(self, Tile::Vertical | Tile::NorthWest | Tile::NorthEast)
     }
 
     fn connects_east(self) -> bool {
-        match self {
-            Tile::Horizontal | Tile::NorthEast | Tile::SouthEast => true,
-            _ => false,
-        }
+        matches!(self, Tile::Horizontal | Tile::NorthEast | Tile::SouthEast)
     }
 
     fn connects_west(self) -> bool {
-        match self {
-            Tile::Horizontal | Tile::NorthWest | Tile::SouthWest => true,
-            _ => false,
-        }
+        matches!(self, Tile::Horizontal | Tile::NorthWest | Tile::SouthWest)
     }
 
     fn connects_south(self) -> bool {
-        match self {
-            Tile::Vertical | Tile::SouthWest | Tile::SouthEast => true,
-            _ => false,
-        }
+        matches!(self, Tile::Vertical | Tile::SouthWest | Tile::SouthEast)
     }
 }
 
@@ -86,7 +76,7 @@ impl Display for Map {
             for tile in row {
                 write!(f, "{}", tile)?;
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
         Ok(())
     }
@@ -125,43 +115,57 @@ impl Map {
         }
     }
 
-    fn replace_start(&mut self) {
-        let mut replacement = Tile::Start;
-
-        for (row, row_tiles) in self.tiles.iter().enumerate() {
-            for (col, tile) in row_tiles.iter().enumerate() {
-                if *tile == Tile::Start {
-                    self.start = (row, col);
-                    let north = self
-                        .north((row, col))
-                        .map_or(false, |pos| self.tile(pos).connects_south());
-                    let east = self
-                        .east((row, col))
-                        .map_or(false, |pos| self.tile(pos).connects_west());
-                    let west = self
-                        .west((row, col))
-                        .map_or(false, |pos| self.tile(pos).connects_east());
-                    let south = self
-                        .south((row, col))
-                        .map_or(false, |pos| self.tile(pos).connects_north());
-                    replacement = match (north, east, south, west) {
-                        (true, false, true, false) => Tile::Vertical,
-                        (false, true, false, true) => Tile::Horizontal,
-                        (true, true, false, false) => Tile::NorthEast,
-                        (true, false, false, true) => Tile::NorthWest,
-                        (false, true, true, false) => Tile::SouthEast,
-                        (false, false, true, true) => Tile::SouthWest,
-                        _ => panic!(
-                            "Unexpected starting position: {:?}",
-                            (north, east, south, west)
-                        ),
-                    };
-                    break;
-                }
+    fn replace_start(&mut self) -> Result<(), SolutionError> {
+        let starts = self
+            .tiles
+            .iter()
+            .enumerate()
+            .flat_map(|(row, row_tiles)| {
+                row_tiles
+                    .iter()
+                    .positions(|tile| *tile == Tile::Start)
+                    .map(move |col| (row, col))
+            })
+            .collect_vec();
+        let (row, col) = match starts[..] {
+            [start] => start,
+            [] => return Err(SolutionError::InvalidInput("No starting position".into())),
+            _ => {
+                return Err(SolutionError::InvalidInput(format!(
+                    "{} starting positions, expected one",
+                    starts.len()
+                )))
             }
-        }
-
-        self.tiles[self.start.0][self.start.1] = replacement;
+        };
+        self.start = (row, col);
+
+        let north = self
+            .north((row, col))
+            .is_some_and(|pos| self.tile(pos).connects_south());
+        let east = self
+            .east((row, col))
+            .is_some_and(|pos| self.tile(pos).connects_west());
+        let west = self
+            .west((row, col))
+            .is_some_and(|pos| self.tile(pos).connects_east());
+        let south = self
+            .south((row, col))
+            .is_some_and(|pos| self.tile(pos).connects_north());
+        self.tiles[row][col] = match (north, east, south, west) {
+            (true, false, true, false) => Tile::Vertical,
+            (false, true, false, true) => Tile::Horizontal,
+            (true, true, false, false) => Tile::NorthEast,
+            (true, false, false, true) => Tile::NorthWest,
+            (false, true, true, false) => Tile::SouthEast,
+            (false, false, true, true) => Tile::SouthWest,
+            _ => {
+                return Err(SolutionError::InvalidInput(format!(
+                    "Unexpected starting position: {:?}",
+                    (north, east, south, west)
+                )))
+            }
+        };
+        Ok(())
     }
 
     fn path<'a>(&'a self) -> impl Iterator<Item = (usize, usize)> + 'a {
@@ -196,11 +200,11 @@ impl Map {
             {
                 west
             } else {
-                panic!("No path from {:?}->{:?}", prev, pos);
-            };
+                None
+            }?;
 
             *prev = *pos;
-            *pos = next.unwrap();
+            *pos = next;
             Some(*prev)
         })
     }
@@ -263,7 +267,8 @@ impl Map {
                         [Tile::Ground, Tile::Ground, Tile::Ground],
                         [Tile::Ground, Tile::Ground, Tile::Ground],
                     ],
-                    Tile::Start => unimplemented!(),
+                    // `replace_start` has succeeded before scaling up, so no start is left
+                    Tile::Start => unreachable!("the start is replaced before scaling up"),
                     Tile::Outside => [
                         [Tile::Outside, Tile::Outside, Tile::Outside],
                         [Tile::Outside, Tile::Outside, Tile::Outside],
@@ -313,11 +318,7 @@ impl Map {
             .collect_vec();
 
         let mut queue = starts;
-        loop {
-            let pos = match queue.pop() {
-                Some(pos) => pos,
-                None => break,
-            };
+        while let Some(pos) = queue.pop() {
             self.tiles[pos.0][pos.1] = Tile::Outside;
 
             let neighbors = [
@@ -327,7 +328,7 @@ impl Map {
                 self.west(pos),
             ]
             .into_iter()
-            .filter_map(|pos| pos);
+            .flatten();
 
             for n in neighbors {
                 let tile = self.tile(n);
@@ -341,13 +342,21 @@ impl Map {
 
 pub struct Day10;
 
+examples! {
+    Day10 {
+        example_1: "examples/day10-1.txt" => { part_1: "8" },
+        example_2: "examples/day10-2.txt" => { part_2: "4" },
+        example_3: "examples/day10-3.txt" => { part_2: "8" },
+    }
+}
+
 impl Day for Day10 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
     type Input = Map;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        let (input, tiles) = separated_list1(
-            multispace1,
-            many1(alt((
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        let (input, tiles) = grid_of(alt((
                 value(Tile::Vertical, char('|')),
                 value(Tile::Horizontal, char('-')),
                 value(Tile::NorthEast, char('L')),
@@ -356,8 +365,7 @@ impl Day for Day10 {
                 value(Tile::SouthEast, char('F')),
                 value(Tile::Ground, char('.')),
                 value(Tile::Start, char('S')),
-            ))),
-        )(input)?;
+        )))(input)?;
 
         Ok((
             input,
@@ -370,34 +378,34 @@ impl Day for Day10 {
 
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
         let mut input = input.clone();
-        input.replace_start();
+        input.replace_start()?;
 
         for (i, pos) in input.path().enumerate() {
             if i > 0 && pos == input.start {
-                return i / 2;
+                return Ok(i / 2);
             }
         }
 
-        unreachable!();
+        Err(SolutionError::NoAnswer)
     }
 
     type Output2 = usize;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
         let mut input = input.clone();
-        input.replace_start();
+        input.replace_start()?;
         input.cleanup();
         input.scale_up();
         input.fill_outside();
         input.scale_down();
 
-        input
+        Ok(input
             .tiles
             .iter()
-            .flat_map(|row| row)
+            .flatten()
             .filter(|tile| **tile == Tile::Ground)
-            .count()
+            .count())
     }
 }
@@ -4,13 +4,15 @@ use array2d::Array2D;
 use itertools::Itertools;
 use nom::{
     branch::alt,
-    character::complete::{char, multispace1},
-    combinator::{all_consuming, map, value},
-    multi::{many1, separated_list1},
-    IResult,
+    character::complete::char,
+    combinator::{map_res, value},
 };
 
-use crate::days::Day;
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::grid_of,
+    parser::ParseResult,
+};
 pub struct Map(Array2D<bool>);
 
 impl Map {
@@ -98,39 +100,33 @@ impl Display for Map {
 
 pub struct Day11;
 
+examples! {
+    Day11 {
+        example: "examples/day11.txt" => { part_1: "374", part_2: "82000210" },
+    }
+}
+
 impl Day for Day11 {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
     type Input = Map;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        // let input = "...#......
-        // .......#..
-        // #.........
-        // ..........
-        // ......#...
-        // .#........
-        // .........#
-        // ..........
-        // .......#..
-        // #...#.....";
-
-        map(
-            all_consuming(separated_list1(
-                multispace1,
-                many1(alt((value(true, char('#')), value(false, char('.'))))),
-            )),
-            |map| Map(Array2D::from_rows(&map).expect("Well formed map")),
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        map_res(
+            grid_of(alt((value(true, char('#')), value(false, char('.'))))),
+            |map| Array2D::from_rows(&map).map(Map),
         )(input)
     }
 
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input.total_travel_distance(2)
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Ok(input.total_travel_distance(2))
     }
 
     type Output2 = usize;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        input.total_travel_distance(1_000_000)
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Ok(input.total_travel_distance(1_000_000))
     }
 }
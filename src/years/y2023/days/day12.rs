@@ -3,14 +3,17 @@ use std::cmp::Ordering;
 use itertools::Itertools;
 use nom::{
     branch::alt,
-    character::complete::{char, multispace1},
-    combinator::{all_consuming, value},
+    character::complete::{char, space1},
+    combinator::value,
     multi::{many1, separated_list1},
     sequence::{preceded, tuple},
-    IResult,
 };
 
-use crate::{days::Day, helpers::parse_digit};
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::{lines_of, parse_digit},
+    parser::ParseResult,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum State {
@@ -21,44 +24,42 @@ pub enum State {
 
 pub struct Day12;
 
+examples! {
+    Day12 {
+        example: "examples/day12.txt" => { part_1: "21" },
+    }
+}
+
 impl Day for Day12 {
-    type Input = Vec<(Vec<State>, Vec<usize>)>;
+    const EXAMPLES: &'static [Example] = EXAMPLES;
 
-    fn parse(input: &str) -> IResult<&str, Self::Input> {
-        // let input = "???.### 1,1,3
-        // .??..??...?##. 1,1,3
-        // ?#?#?#?#?#?#?#? 1,3,1,6
-        // ????.#...#... 4,1,1
-        // ????.######..#####. 1,6,5
-        // ?###???????? 3,2,1";
+    type Input = Vec<(Vec<State>, Vec<usize>)>;
 
-        all_consuming(separated_list1(
-            multispace1,
-            tuple((
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        lines_of(tuple((
                 many1(alt((
                     value(State::Operational, char('.')),
                     value(State::Damaged, char('#')),
                     value(State::Unknown, char('?')),
                 ))),
                 preceded(
-                    multispace1,
+                    space1,
                     separated_list1(char(','), parse_digit("group")),
                 ),
-            )),
-        ))(input)
+            )))(input)
     }
 
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        sum_combinations(input.into_iter())
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Ok(sum_combinations(input.iter()))
     }
 
     type Output2 = usize;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, SolutionError> {
         let unfolded = input
-            .into_iter()
+            .iter()
             .map(|(springs, groups)| {
                 let springs = [
                     &springs[..],
@@ -85,7 +86,7 @@ impl Day for Day12 {
                 (springs, groups)
             })
             .collect_vec();
-        sum_combinations(unfolded.iter())
+        Ok(sum_combinations(unfolded.iter()))
     }
 }
 
@@ -97,9 +98,9 @@ where
         .map(|(springs, groups)| {
             let padded = [&[State::Operational], &springs[..], &[State::Operational]].concat();
             let damaged = springs
-                .into_iter()
+                .iter()
                 .enumerate()
-                .filter_map(|(index, s)| (*s == State::Damaged).then(|| index))
+                .filter_map(|(index, s)| (*s == State::Damaged).then_some(index))
                 .collect_vec();
             let variants = groups
                 .iter()
@@ -120,10 +121,10 @@ where
                             let last = window[window.len() - 1];
                             ((first == State::Operational || first == State::Unknown)
                                 && window[1..(window.len() - 1)]
-                                    .into_iter()
+                                    .iter()
                                     .all(|&s| s == State::Damaged || s == State::Unknown)
                                 && (last == State::Operational || last == State::Unknown))
-                                .then(move || (index, group))
+                                .then_some((index, group))
                         })
                 })
                 .multi_cartesian_product()
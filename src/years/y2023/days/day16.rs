@@ -0,0 +1,26 @@
+use crate::{
+    days::{Day, SolutionError},
+    parser::ParseResult,
+};
+
+pub struct Day16;
+
+impl Day for Day16 {
+    type Input = String;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        Ok(("", input.to_owned()))
+    }
+
+    type Output1 = usize;
+
+    fn part_1(_input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Err(SolutionError::Unsolved)
+    }
+
+    type Output2 = usize;
+
+    fn part_2(_input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Err(SolutionError::Unsolved)
+    }
+}
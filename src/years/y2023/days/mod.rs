@@ -0,0 +1,30 @@
+use crate::days::days;
+
+days! {
+    year = 2023;
+    day01::Day01 = 1,
+    day02::Day02 = 2,
+    day03::Day03 = 3,
+    day04::Day04 = 4,
+    day05::Day05 = 5,
+    day06::Day06 = 6,
+    day07::Day07 = 7,
+    day08::Day08 = 8,
+    day09::Day09 = 9,
+    day10::Day10 = 10,
+    day11::Day11 = 11,
+    day12::Day12 = 12,
+    day13::Day13 = 13,
+    day14::Day14 = 14,
+    day15::Day15 = 15,
+    day16::Day16 = 16,
+    day17::Day17 = 17,
+    day18::Day18 = 18,
+    day19::Day19 = 19,
+    day20::Day20 = 20,
+    day21::Day21 = 21,
+    day22::Day22 = 22,
+    day23::Day23 = 23,
+    day24::Day24 = 24,
+    day25::Day25 = 25,
+}
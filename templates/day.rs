@@ -0,0 +1,37 @@
+use nom::{character::complete::not_line_ending, combinator::map};
+
+use crate::{
+    days::{examples, Day, Example, SolutionError},
+    helpers::lines_of,
+    parser::ParseResult,
+};
+
+pub struct Day{{DAY}};
+
+examples! {
+    Day{{DAY}} {
+        example_1: "examples/day{{DAY}}-1.txt" => {},
+    }
+}
+
+impl Day for Day{{DAY}} {
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        lines_of(map(not_line_ending, str::to_owned))(input)
+    }
+
+    type Output1 = usize;
+
+    fn part_1(_input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        Err(SolutionError::Unsolved)
+    }
+
+    type Output2 = usize;
+
+    fn part_2(_input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Err(SolutionError::Unsolved)
+    }
+}